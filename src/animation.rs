@@ -0,0 +1,386 @@
+//! Step-by-step playback of puzzle simulations in the terminal.
+//!
+//! A simulation implements [`Animation`] by advancing one step at a time and drawing its current
+//! state as text, and a [`Player`] redraws it in place at a configurable speed. When standard
+//! input is a terminal, playback can be controlled with the keys listed in [`CONTROLS`].
+
+use crate::{day05, day09, day12, day14, locate_parse_error, stdin_isatty, Solution};
+use anyhow::{Error, Result};
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+/// A simulation that can be played back one step at a time.
+pub trait Animation {
+    /// Advances the simulation by one step, returning `false` if it has already finished.
+    fn step(&mut self) -> Result<bool>;
+
+    /// Draws the current state, one line of text per row.
+    fn frame(&self) -> String;
+
+    /// A short description of the current state, shown below the frame.
+    fn caption(&self) -> String {
+        String::new()
+    }
+}
+
+/// The animation of a part of a puzzle, for the days that have one.
+pub fn animation(day: u8, part: u8, input: &str) -> Result<Box<dyn Animation>> {
+    if !(1..=2).contains(&part) {
+        return Err(Error::msg(format!("invalid part {part}")));
+    }
+    let animation: Box<dyn Animation> = match day {
+        5 => {
+            let (stacks, steps) = parse::<day05::Day05>(input)?;
+            let model = match part {
+                1 => day05::CrateMover9000,
+                _ => day05::CrateMover9001,
+            };
+            Box::new(day05::Rearrangement::new(stacks, steps, model))
+        }
+        9 => {
+            let len = if part == 1 { 2 } else { 10 };
+            Box::new(day09::RopeMotion::new(parse::<day09::Day09>(input)?, len)?)
+        }
+        12 => {
+            let map = parse::<day12::Day12>(input)?;
+            let finished = match part {
+                1 => day12::Map::is_start,
+                _ => day12::Map::is_height_a,
+            };
+            Box::new(day12::Climb::new(map, finished))
+        }
+        14 => {
+            let mut map = parse::<day14::Day14>(input)?;
+            map.floor = part == 2;
+            Box::new(day14::SandFall::new(map))
+        }
+        _ => return Err(Error::msg(format!("no animation for day {day}"))),
+    };
+    Ok(animation)
+}
+
+fn parse<S: Solution>(input: &str) -> Result<S::Parsed> {
+    S::parse(input).map_err(|e| locate_parse_error(input, e))
+}
+
+/// The keys controlling the playback, as shown below the frame.
+pub const CONTROLS: &str = "space: pause  n: step  +/-: speed  </>: skip frames  q: quit";
+
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 1000.0;
+
+/// Plays an animation in the terminal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Player {
+    /// The number of frames drawn per second.
+    pub fps: f64,
+    /// The number of steps between two frames, i.e. one more than the number of skipped frames.
+    pub steps_per_frame: usize,
+    pub paused: bool,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self {
+            fps: 20.0,
+            steps_per_frame: 1,
+            paused: false,
+        }
+    }
+}
+
+/// What to do after a key was pressed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Command {
+    Redraw,
+    Step,
+    Quit,
+}
+
+impl Player {
+    /// Plays `animation` until it has finished, or until playback is stopped with `q` if
+    /// standard input is a terminal.
+    pub fn play(&mut self, animation: &mut dyn Animation) -> Result<()> {
+        let terminal = RawTerminal::enable();
+        let mut out = std::io::stdout().lock();
+        // Hide the cursor and clear the screen.
+        write!(out, "\x1b[?25l\x1b[2J")?;
+        let result = self.run(animation, terminal.is_some(), &mut out);
+        writeln!(out, "\x1b[?25h")?;
+        result
+    }
+
+    fn run(
+        &mut self,
+        animation: &mut dyn Animation,
+        interactive: bool,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let mut steps = 0;
+        let mut finished = false;
+        loop {
+            let start = Instant::now();
+            self.draw(animation, steps, finished, out)?;
+            let count = if interactive {
+                let timeout = (!self.paused && !finished).then(|| self.frame_time());
+                match read_key(timeout)? {
+                    None => self.steps_per_frame,
+                    Some(key) => match self.handle_key(key) {
+                        Command::Redraw => 0,
+                        Command::Step => 1,
+                        Command::Quit => return Ok(()),
+                    },
+                }
+            } else if finished {
+                return Ok(());
+            } else {
+                std::thread::sleep(self.frame_time().saturating_sub(start.elapsed()));
+                self.steps_per_frame
+            };
+            for _ in 0..count {
+                if finished || !animation.step()? {
+                    finished = true;
+                    break;
+                }
+                steps += 1;
+            }
+        }
+    }
+
+    fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.fps)
+    }
+
+    fn handle_key(&mut self, key: u8) -> Command {
+        match key {
+            b' ' | b'p' => self.paused = !self.paused,
+            b'n' | b'.' => {
+                self.paused = true;
+                return Command::Step;
+            }
+            b'+' | b'=' => self.fps = (self.fps * 2.0).min(MAX_FPS),
+            b'-' => self.fps = (self.fps / 2.0).max(MIN_FPS),
+            b'>' => self.steps_per_frame = self.steps_per_frame.saturating_mul(2),
+            b'<' => self.steps_per_frame = (self.steps_per_frame / 2).max(1),
+            // Ctrl-C arrives as a key, since signals are disabled while playing.
+            b'q' | 3 => return Command::Quit,
+            _ => {}
+        }
+        Command::Redraw
+    }
+
+    fn status(&self, steps: usize, finished: bool) -> String {
+        let state = if finished {
+            "  finished"
+        } else if self.paused {
+            "  paused"
+        } else {
+            ""
+        };
+        format!(
+            "step {steps}  {} fps  {} steps/frame{state}",
+            self.fps, self.steps_per_frame
+        )
+    }
+
+    /// Redraws the screen, cropping the frame to the size of the terminal.
+    fn draw(
+        &self,
+        animation: &dyn Animation,
+        steps: usize,
+        finished: bool,
+        out: &mut impl Write,
+    ) -> Result<()> {
+        let (width, height) = terminal_size().unwrap_or((usize::MAX, usize::MAX));
+        let mut lines: Vec<String> = animation.frame().lines().map(str::to_owned).collect();
+        lines.truncate(height.saturating_sub(3));
+        lines.push(animation.caption());
+        lines.push(self.status(steps, finished));
+        lines.push(CONTROLS.to_owned());
+        let mut screen = String::from("\x1b[H");
+        for line in lines {
+            screen.extend(line.chars().take(width));
+            screen.push_str("\x1b[K\n");
+        }
+        // Clear whatever is left of the previous frame.
+        screen.push_str("\x1b[J");
+        out.write_all(screen.as_bytes())?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Puts the terminal into a mode where key presses are read immediately without being echoed,
+/// restoring the previous mode when dropped.
+struct RawTerminal(libc::termios);
+
+impl RawTerminal {
+    fn enable() -> Option<Self> {
+        if !stdin_isatty() {
+            return None;
+        }
+        unsafe {
+            let mut original = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return None;
+            }
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return None;
+            }
+            Some(Self(original))
+        }
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+        }
+    }
+}
+
+/// Waits for a key press until `timeout` has passed, or indefinitely if it is `None`.
+fn read_key(timeout: Option<Duration>) -> Result<Option<u8>> {
+    let mut fds = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout = timeout.map_or(-1, |t| t.as_millis().min(i32::MAX as u128) as i32);
+    let ready = unsafe { libc::poll(&mut fds, 1, timeout) };
+    if ready < 0 {
+        let error = std::io::Error::last_os_error();
+        if error.kind() == std::io::ErrorKind::Interrupted {
+            return Ok(None);
+        }
+        return Err(error.into());
+    }
+    if ready == 0 {
+        return Ok(None);
+    }
+    let mut key = 0u8;
+    let n = unsafe { libc::read(libc::STDIN_FILENO, (&mut key as *mut u8).cast(), 1) };
+    Ok((n == 1).then_some(key))
+}
+
+/// The width and height of the terminal in characters.
+fn terminal_size() -> Option<(usize, usize)> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } == 0;
+    (ok && size.ws_col > 0 && size.ws_row > 0)
+        .then_some((usize::from(size.ws_col), usize::from(size.ws_row)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::example_path;
+
+    /// Counts up to a limit.
+    struct Counter(u32, u32);
+
+    impl Animation for Counter {
+        fn step(&mut self) -> Result<bool> {
+            if self.0 == self.1 {
+                return Ok(false);
+            }
+            self.0 += 1;
+            Ok(true)
+        }
+
+        fn frame(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn play_without_terminal() {
+        let mut player = Player {
+            fps: MAX_FPS,
+            steps_per_frame: 3,
+            paused: false,
+        };
+        let mut counter = Counter(0, 10);
+        let mut out = vec![];
+        player.run(&mut counter, false, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let frames: Vec<&str> = out
+            .split("\x1b[H")
+            .skip(1)
+            .map(|screen| screen.split("\x1b[K").next().unwrap())
+            .collect();
+        assert_eq!(frames, ["0", "3", "6", "9", "10"]);
+        assert!(out.contains("step 10  1000 fps  3 steps/frame  finished"));
+    }
+
+    #[test]
+    fn keys() {
+        let mut player = Player::default();
+        assert_eq!(player.handle_key(b' '), Command::Redraw);
+        assert!(player.paused);
+        assert_eq!(player.handle_key(b'n'), Command::Step);
+        assert!(player.paused);
+        player.handle_key(b'+');
+        assert_eq!(player.fps, 40.0);
+        for _ in 0..20 {
+            player.handle_key(b'-');
+        }
+        assert_eq!(player.fps, MIN_FPS);
+        player.handle_key(b'>');
+        player.handle_key(b'>');
+        assert_eq!(player.steps_per_frame, 4);
+        for _ in 0..3 {
+            player.handle_key(b'<');
+        }
+        assert_eq!(player.steps_per_frame, 1);
+        assert_eq!(player.handle_key(b'q'), Command::Quit);
+        assert_eq!(
+            player.status(5, false),
+            "step 5  0.25 fps  1 steps/frame  paused"
+        );
+    }
+
+    /// Runs the animation of an example to the end and returns its last frame and caption.
+    fn last_frame(day: u8, part: u8) -> (String, String) {
+        let path = example_path(&format!("day{day:02}"), 1);
+        let input = std::fs::read_to_string(path).unwrap();
+        let mut animation = animation(day, part, &input).unwrap();
+        while animation.step().unwrap() {}
+        (animation.frame(), animation.caption())
+    }
+
+    #[test]
+    fn days() {
+        let (frame, caption) = last_frame(5, 1);
+        assert_eq!(frame.lines().last().unwrap(), " 1   2   3 ");
+        assert_eq!(caption, "4 of 4 steps, top crates CMZ");
+        assert_eq!(last_frame(5, 2).1, "4 of 4 steps, top crates MCD");
+
+        assert_eq!(last_frame(9, 1).1, "24 steps, tail visited 13 positions");
+        assert_eq!(last_frame(9, 2).1, "24 steps, tail visited 1 positions");
+
+        let (frame, caption) = last_frame(12, 1);
+        assert_eq!(caption, "distance 31, path found");
+        assert_eq!(frame.matches('#').count(), 30);
+        assert_eq!(last_frame(12, 2).0.matches('#').count(), 29);
+
+        let (frame, caption) = last_frame(14, 1);
+        assert_eq!(
+            caption,
+            "24 units of sand at rest, the sand flows into the void"
+        );
+        assert_eq!(frame.matches('o').count(), 24);
+        let (frame, caption) = last_frame(14, 2);
+        assert_eq!(caption, "93 units of sand at rest, the source is blocked");
+        assert_eq!(frame.matches('o').count(), 93);
+
+        assert!(animation(1, 1, "").is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! Recorded puzzle answers.
+//!
+//! Answers are stored one part per line as `Part 1: 24000`. A multi-line answer is written as
+//! `Part 2:`, followed by the lines of the answer, each indented by four spaces. Parts may be
+//! omitted, e.g. when an example only states the answer for one of the parts.
+//!
+//! For other programs, answers can also be written as JSON records, see [`AnswerRecord`].
+
+use crate::{ocr, Puzzle};
+use anyhow::{Context, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+const INDENT: &str = "    ";
+
+/// The answers for some or all parts of a puzzle, as `(part, answer)` pairs.
+pub type Answers = Vec<(u8, String)>;
+
+pub fn format_answers(answers: &[(u8, String)]) -> String {
+    let mut s = String::new();
+    for (part, answer) in answers {
+        if answer.contains('\n') {
+            s.push_str(&format!("Part {part}:\n"));
+            for line in answer.lines() {
+                s.push_str(&format!("{INDENT}{line}\n"));
+            }
+        } else {
+            s.push_str(&format!("Part {part}: {answer}\n"));
+        }
+    }
+    s
+}
+
+pub fn parse_answers(s: &str) -> Result<Answers> {
+    let mut answers: Answers = vec![];
+    for line in s.lines() {
+        if let Some(continuation) = line.strip_prefix(INDENT) {
+            let (_, answer) = answers
+                .last_mut()
+                .context("indented line before the first answer")?;
+            if !answer.is_empty() {
+                answer.push('\n');
+            }
+            answer.push_str(continuation);
+        } else if let Some(rest) = line.strip_prefix("Part ") {
+            let (part, answer) = rest.split_once(':').context("expected colon after part")?;
+            answers.push((part.parse()?, answer.trim().to_owned()));
+        } else if !line.trim().is_empty() {
+            return Err(Error::msg(format!("invalid answer line {line:?}")));
+        }
+    }
+    Ok(answers)
+}
+
+/// The path of the answers file recorded for the input file at `input_path`.
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    let mut path = input_path.as_os_str().to_owned();
+    path.push(".answers");
+    path.into()
+}
+
+pub fn read_answers(input_path: &Path) -> Result<Answers> {
+    let path = answers_path(input_path);
+    let s =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    parse_answers(&s).with_context(|| format!("invalid answers file {}", path.display()))
+}
+
+/// The answer to one part of a puzzle, with the time it took to compute it.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AnswerRecord {
+    pub day: u8,
+    pub part: u8,
+    /// The answer, with the letters of a multi-line image decoded if they can be recognized.
+    pub answer: String,
+    /// The lines of a multi-line answer as drawn by the puzzle, or `None` for other answers.
+    pub rows: Option<Vec<String>>,
+    /// The time taken by the part in nanoseconds, not including the parsing of the input.
+    pub duration: u64,
+}
+
+impl AnswerRecord {
+    pub fn new(day: u8, part: u8, answer: String, duration: Duration) -> Self {
+        let (answer, rows) = if answer.contains('\n') {
+            let rows = answer.lines().map(str::to_owned).collect();
+            (ocr::decode(&answer).unwrap_or(answer), Some(rows))
+        } else {
+            (answer, None)
+        };
+        Self {
+            day,
+            part,
+            answer,
+            rows,
+            duration: duration.as_nanos() as u64,
+        }
+    }
+}
+
+/// Solves both parts of `puzzle`, timing each part.
+pub fn solve_timed(puzzle: &Puzzle, input: &str) -> Result<[AnswerRecord; 2]> {
+    let parsed = puzzle.parse(input)?;
+    let start = Instant::now();
+    let part1 = puzzle.part1(&*parsed)?;
+    let part1 = AnswerRecord::new(puzzle.day(), 1, part1, start.elapsed());
+    let start = Instant::now();
+    let part2 = puzzle.part2(&*parsed)?;
+    let part2 = AnswerRecord::new(puzzle.day(), 2, part2, start.elapsed());
+    Ok([part1, part2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let answers = vec![(1, "42".to_owned()), (2, "# #\n # ".to_owned())];
+        let formatted = format_answers(&answers);
+        assert_eq!(formatted, "Part 1: 42\nPart 2:\n    # #\n     # \n");
+        assert_eq!(parse_answers(&formatted).unwrap(), answers);
+    }
+
+    #[test]
+    fn missing_parts() {
+        let answers = parse_answers("Part 2: 36\n").unwrap();
+        assert_eq!(answers, vec![(2, "36".to_owned())]);
+    }
+
+    #[test]
+    fn records() {
+        let record = AnswerRecord::new(3, 1, "42".to_owned(), Duration::from_micros(5));
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"day":3,"part":1,"answer":"42","rows":null,"duration":5000}"#
+        );
+
+        let image = "#..#.#...\n#..#.#...\n####.#...\n#..#.#...\n#..#.#...\n#..#.####";
+        let record = AnswerRecord::new(10, 2, image.to_owned(), Duration::ZERO);
+        assert_eq!(record.answer, "HL");
+        assert_eq!(record.rows.unwrap()[5], "#..#.####");
+
+        // Images that cannot be read are kept as they are.
+        let record = AnswerRecord::new(10, 2, "#\n#".to_owned(), Duration::ZERO);
+        assert_eq!(record.answer, "#\n#");
+        assert_eq!(record.rows.unwrap(), ["#", "#"]);
+    }
+}
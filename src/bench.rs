@@ -0,0 +1,251 @@
+//! Benchmarks of the parsing phase and both parts of each puzzle.
+
+use crate::Puzzle;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// The phases of a puzzle that are timed separately.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part 1",
+            Phase::Part2 => "part 2",
+        };
+        name.fmt(f)
+    }
+}
+
+/// Summary statistics of the run times of a single phase, in nanoseconds.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        let total: Duration = samples.iter().sum();
+        Self {
+            min_ns: samples[0].as_nanos() as u64,
+            median_ns: median.as_nanos() as u64,
+            mean_ns: (total.as_nanos() / n as u128) as u64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PhaseTimings {
+    pub phase: Phase,
+    #[serde(flatten)]
+    pub stats: Stats,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DayTimings {
+    pub day: u8,
+    pub phases: Vec<PhaseTimings>,
+}
+
+impl DayTimings {
+    pub fn get(&self, phase: Phase) -> Option<&Stats> {
+        self.phases
+            .iter()
+            .find(|t| t.phase == phase)
+            .map(|t| &t.stats)
+    }
+}
+
+/// The results of a benchmark run, which can be saved and later used as a baseline.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Report {
+    pub runs: usize,
+    pub days: Vec<DayTimings>,
+}
+
+impl Report {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("invalid report {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_json()? + "\n")
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Compares the median times against `baseline` and returns all phases that got slower by
+    /// more than `threshold`, e.g. 0.1 for 10%.
+    pub fn regressions(&self, baseline: &Report, threshold: f64) -> Vec<Regression> {
+        let mut regressions = vec![];
+        for current in &self.days {
+            let Some(base) = baseline.days.iter().find(|b| b.day == current.day) else {
+                continue;
+            };
+            for timings in &current.phases {
+                let Some(base_stats) = base.get(timings.phase) else {
+                    continue;
+                };
+                let ratio = timings.stats.median_ns as f64 / base_stats.median_ns.max(1) as f64;
+                if ratio > 1.0 + threshold {
+                    regressions.push(Regression {
+                        day: current.day,
+                        phase: timings.phase,
+                        baseline_ns: base_stats.median_ns,
+                        current_ns: timings.stats.median_ns,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub phase: Phase,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} {}: {} -> {} ({:+.1}%)",
+            self.day,
+            self.phase,
+            format_ns(self.baseline_ns),
+            format_ns(self.current_ns),
+            (self.current_ns as f64 / self.baseline_ns.max(1) as f64 - 1.0) * 100.0
+        )
+    }
+}
+
+/// Runs each phase of `puzzle` `runs` times, after a single warm-up run.
+pub fn bench_puzzle(puzzle: &Puzzle, input: &str, runs: usize) -> Result<DayTimings> {
+    assert!(runs > 0);
+    let parsed = puzzle.parse(input)?;
+    let phases = Phase::ALL
+        .into_iter()
+        .map(|phase| {
+            let run = || -> Result<()> {
+                match phase {
+                    Phase::Parse => drop(puzzle.parse(input)?),
+                    Phase::Part1 => drop(puzzle.part1(&*parsed)?),
+                    Phase::Part2 => drop(puzzle.part2(&*parsed)?),
+                }
+                Ok(())
+            };
+            run()?;
+            let mut samples = (0..runs)
+                .map(|_| {
+                    let start = Instant::now();
+                    run()?;
+                    Ok(start.elapsed())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let stats = Stats::from_samples(&mut samples);
+            Ok(PhaseTimings { phase, stats })
+        })
+        .collect::<Result<_>>()?;
+    Ok(DayTimings {
+        day: puzzle.day(),
+        phases,
+    })
+}
+
+/// Formats a duration in nanoseconds with a unit suitable for its magnitude.
+pub fn format_ns(ns: u64) -> String {
+    match ns {
+        0..=999 => format!("{ns} ns"),
+        1_000..=999_999 => format!("{:.1} µs", ns as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1} ms", ns as f64 / 1e6),
+        _ => format!("{:.2} s", ns as f64 / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(median_ns: u64) -> Report {
+        let stats = Stats {
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+        };
+        Report {
+            runs: 1,
+            days: vec![DayTimings {
+                day: 1,
+                phases: vec![PhaseTimings {
+                    phase: Phase::Part1,
+                    stats,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn stats() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(
+            stats,
+            Stats {
+                min_ns: 1,
+                median_ns: 2,
+                mean_ns: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = report(1000);
+        assert!(report(1050).regressions(&baseline, 0.1).is_empty());
+        let regressions = report(1200).regressions(&baseline, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].phase, Phase::Part1);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let report = report(1000);
+        let json = report.to_json().unwrap();
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
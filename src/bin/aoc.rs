@@ -0,0 +1,495 @@
+use anyhow::{Context, Error, Result};
+use aoc2022::{
+    answers::{format_answers, solve_timed, Answers},
+    bench::{bench_puzzle, format_ns, Report},
+    client::{guesses_path, Client},
+    fuzz::fuzz,
+    generate::generate,
+    image::{self, puzzle_frames, scale_up, Frames, Palette},
+    input_path, puzzle,
+    scaffold::{current_day, default_year, new_day},
+    DaySelection, InputSource, Puzzle, INPUT_OPTIONS_USAGE, PUZZLES,
+};
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: aoc [INPUT OPTIONS] [--format text|json] [DAYS...]
+       aoc bench [INPUT OPTIONS] [--runs N] [--format text|json]
+                 [--baseline FILE] [--threshold PERCENT] [--save-baseline FILE]
+                 [DAYS...]
+       aoc image [INPUT OPTIONS] [--scale N] [--gradient COLOURS]
+                 [--color NAME=COLOUR]... DAY [PART] FILE
+       aoc image [INPUT OPTIONS] [...] [--every N] --frames PATTERN DAY [PART]
+       aoc generate [--seed N] [--scale N] DAY
+       aoc fuzz [--seeds N] [--scale N] [DAYS...]
+       aoc new [DAY] [--year YEAR]
+       aoc download DAY [--year YEAR]
+       aoc submit DAY PART [ANSWER] [--year YEAR]
+
+DAYS can be a single day (7), a range (1..=14 or 1..15) or `all`.
+Without arguments, the latest day is run. By default, the input of each day is
+read from inputs/dayNN.
+
+With --format json, a JSON record {\"day\", \"part\", \"answer\", \"rows\", \"duration\"}
+is printed on a separate line for each part. The duration of the part is given
+in nanoseconds, without parsing. Multi-line answers are given as their lines in
+\"rows\", and \"answer\" holds the letters read from them, or the lines joined
+with newlines if the letters are not recognized. Otherwise, \"rows\" is null.
+
+The bench subcommand times parsing and both parts separately, reporting the
+minimum, median and mean over N runs (default 20). With --format json, the
+report is printed in the format saved by --save-baseline. With --baseline,
+median times are compared against a report saved with --save-baseline, and
+phases slower by more than PERCENT (default 10) are reported as regressions.
+
+The image subcommand draws the state after solving part 1 or 2 (default 1) of
+day 8 (tree heights or scenic scores), day 12 (height map with the shortest
+path) or day 14 (sand at rest) as a .ppm or, with the png feature, a .png file.
+Each cell becomes a square of N pixels with --scale N. --gradient takes the
+colours used for heights and scores, e.g. #000000,#ff8000,#ffffff, and --color
+changes one of the colours path, rock, sand and air. With --frames, a numbered
+sequence of images is written instead, e.g. frames/sand-00000.png, ... for the
+pattern frames/sand.png, keeping every N-th frame (default 1).
+
+The generate subcommand prints a random input for a day, made from the seed N
+(default 0). The size of the input grows with --scale (default 20), which is
+roughly the number of lines or items. The fuzz subcommand solves the inputs
+generated from the seeds 0 to N - 1 (default 100) for each day, and fails if a
+solution panics, returns an error, or disagrees with the slower reference
+implementations that some days have. Without DAYS, all days are fuzzed.
+
+The new subcommand creates the module, binary and example input placeholders
+for a new day and registers it in the library and the answer tests. Existing
+files are never overwritten. Without DAY, today's puzzle is created.
+
+The download subcommand saves the puzzle input as inputs/dayNN, and the submit
+subcommand submits an answer, by default the one computed from inputs/dayNN.
+Both use the session token from $ADVENT_OF_CODE_SESSION or
+~/.adventofcode.session. Submitted answers are recorded in
+inputs/dayNN.guesses, and answers known to be wrong are not submitted again.";
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("-h" | "--help" | "help") => {
+            println!("{USAGE}\n\n{INPUT_OPTIONS_USAGE}");
+            Ok(())
+        }
+        Some("bench") => {
+            args.next();
+            bench(args)
+        }
+        Some("image") => {
+            args.next();
+            image(args)
+        }
+        Some("generate") => {
+            args.next();
+            generate_input(args)
+        }
+        Some("fuzz") => {
+            args.next();
+            fuzz_days(args)
+        }
+        Some("new") => {
+            args.next();
+            new(args)
+        }
+        Some("download") => {
+            args.next();
+            download(args)
+        }
+        Some("submit") => {
+            args.next();
+            submit(args)
+        }
+        _ => {
+            let (source, args) = input_source(args)?;
+            let (format, days) = format_args(args)?;
+            for puzzle in select_puzzles(days, &source)? {
+                run(puzzle, &source, format)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Extracts the `--format` option from `args`.
+fn format_args(args: impl IntoIterator<Item = String>) -> Result<(Format, Vec<String>)> {
+    let mut format = Format::Text;
+    let mut rest = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args
+                    .next()
+                    .with_context(|| format!("missing value for --format\n\n{USAGE}"))?;
+                format = parse_format(&value)?;
+            }
+            _ if arg.starts_with('-') => {
+                return Err(Error::msg(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => rest.push(arg),
+        }
+    }
+    Ok((format, rest))
+}
+
+fn parse_format(value: &str) -> Result<Format> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(Error::msg(format!(
+            "invalid format {value:?}, expected text or json\n\n{USAGE}"
+        ))),
+    }
+}
+
+/// Extracts the input options from `args`. Unlike the day binaries, `aoc` only reads standard
+/// input when asked to.
+fn input_source(args: impl IntoIterator<Item = String>) -> Result<(InputSource, Vec<String>)> {
+    let (source, rest) = InputSource::from_args(args)?;
+    let source = match source {
+        InputSource::Auto => InputSource::File,
+        source => source,
+    };
+    Ok((source, rest))
+}
+
+fn select_puzzles(
+    args: impl IntoIterator<Item = String>,
+    source: &InputSource,
+) -> Result<Vec<&'static Puzzle>> {
+    let selections = args
+        .into_iter()
+        .map(|arg| {
+            arg.parse()
+                .with_context(|| format!("invalid day selection {arg:?}\n\n{USAGE}"))
+        })
+        .collect::<Result<Vec<DaySelection>>>()?;
+    let puzzles: Vec<&Puzzle> = if selections.is_empty() {
+        PUZZLES.last().into_iter().collect()
+    } else {
+        PUZZLES
+            .iter()
+            .filter(|p| selections.iter().any(|s| s.contains(p.day())))
+            .collect()
+    };
+    if puzzles.is_empty() {
+        return Err(Error::msg("no solutions for the selected days"));
+    }
+    if source.is_fixed() && puzzles.len() > 1 {
+        return Err(Error::msg(
+            "a single input file can only be used with a single day",
+        ));
+    }
+    Ok(puzzles)
+}
+
+fn run(puzzle: &Puzzle, source: &InputSource, format: Format) -> Result<()> {
+    let input = source.read(&puzzle.name())?;
+    if format == Format::Json {
+        let records =
+            solve_timed(puzzle, &input).with_context(|| format!("day {} failed", puzzle.day()))?;
+        for record in records {
+            println!("{}", serde_json::to_string(&record)?);
+        }
+        return Ok(());
+    }
+    println!("Day {}", puzzle.day());
+    let answers = puzzle
+        .solve(&input)
+        .with_context(|| format!("day {} failed", puzzle.day()))?;
+    let answers: Answers = (1..).zip(answers).collect();
+    for line in format_answers(&answers).lines() {
+        println!("  {line}");
+    }
+    Ok(())
+}
+
+fn bench(args: impl Iterator<Item = String>) -> Result<()> {
+    let (source, args) = input_source(args)?;
+    let mut args = args.into_iter();
+    let mut runs = 20;
+    let mut format = Format::Text;
+    let mut baseline = None;
+    let mut save_baseline = None;
+    let mut threshold = 10.0;
+    let mut days = vec![];
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("missing value for {arg}\n\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--runs" => runs = value()?.parse()?,
+            "--format" => format = parse_format(&value()?)?,
+            "--baseline" => baseline = Some(PathBuf::from(value()?)),
+            "--save-baseline" => save_baseline = Some(PathBuf::from(value()?)),
+            "--threshold" => threshold = value()?.parse()?,
+            _ if arg.starts_with('-') => {
+                return Err(Error::msg(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => days.push(arg),
+        }
+    }
+    if runs == 0 {
+        return Err(Error::msg("the number of runs must be positive"));
+    }
+
+    let mut report = Report { runs, days: vec![] };
+    for puzzle in select_puzzles(days, &source)? {
+        let input = source.read(&puzzle.name())?;
+        let timings = bench_puzzle(puzzle, &input, runs)
+            .with_context(|| format!("day {} failed", puzzle.day()))?;
+        if format == Format::Text {
+            for t in &timings.phases {
+                println!(
+                    "day {:2} {:6}  min {:>10}  median {:>10}  mean {:>10}",
+                    timings.day,
+                    t.phase.to_string(),
+                    format_ns(t.stats.min_ns),
+                    format_ns(t.stats.median_ns),
+                    format_ns(t.stats.mean_ns),
+                );
+            }
+        }
+        report.days.push(timings);
+    }
+    if format == Format::Json {
+        println!("{}", report.to_json()?);
+    }
+    if let Some(path) = save_baseline {
+        report.save(&path)?;
+    }
+    if let Some(path) = baseline {
+        let regressions = report.regressions(&Report::load(&path)?, threshold / 100.0);
+        if !regressions.is_empty() {
+            for regression in &regressions {
+                eprintln!("regression: {regression}");
+            }
+            return Err(Error::msg(format!(
+                "{} phases are more than {threshold}% slower than the baseline",
+                regressions.len()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn image(args: impl Iterator<Item = String>) -> Result<()> {
+    let (source, args) = input_source(args)?;
+    let mut args = args.into_iter();
+    let mut palette = Palette::default();
+    let mut scale = 1;
+    let mut frames = None;
+    let mut every = 1;
+    let mut positional = vec![];
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("missing value for {arg}\n\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--scale" => scale = value()?.parse()?,
+            "--gradient" => palette.gradient = value()?.parse()?,
+            "--color" => {
+                let value = value()?;
+                let (name, color) = value
+                    .split_once('=')
+                    .with_context(|| format!("expected NAME=COLOUR, found {value:?}"))?;
+                palette.set(name, color.parse()?)?;
+            }
+            "--frames" => frames = Some(PathBuf::from(value()?)),
+            "--every" => every = value()?.parse()?,
+            _ if arg.starts_with('-') => {
+                return Err(Error::msg(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let (day, part, output) = match (positional.as_slice(), &frames) {
+        ([day], Some(_)) => (day, "1", None),
+        ([day, part], Some(_)) => (day, part.as_str(), None),
+        ([day, output], None) => (day, "1", Some(output)),
+        ([day, part, output], None) => (day, part.as_str(), Some(output)),
+        _ => return Err(Error::msg(format!("expected DAY [PART] FILE\n\n{USAGE}"))),
+    };
+    let day = parse_day(Some(day))?;
+    let part = part
+        .parse()
+        .with_context(|| format!("invalid part {part:?}"))?;
+    let input = source.read(&format!("day{day:02}"))?;
+    if let Some(pattern) = frames {
+        let mut frames = Frames::new(&pattern)?;
+        puzzle_frames(day, part, &input, &palette, every, |frame| {
+            frames.write(&scale_up(&frame, scale)).map(drop)
+        })?;
+        println!("Wrote {} frames like {}", frames.count(), pattern.display());
+    } else if let Some(output) = output {
+        let frame = image::puzzle_image(day, part, &input, &palette)?;
+        image::save(&scale_up(&frame, scale), Path::new(output))?;
+        println!("Wrote {output}");
+    }
+    Ok(())
+}
+
+/// Extracts the options of `generate` and `fuzz`, i.e. the scale and the option named `seed`.
+fn generator_args(
+    mut args: impl Iterator<Item = String>,
+    seed: &str,
+    default_seed: u64,
+) -> Result<(u64, usize, Vec<String>)> {
+    let mut seed_value = default_seed;
+    let mut scale = 20;
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("missing value for {arg}\n\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "--scale" => scale = value()?.parse()?,
+            _ if arg == seed => seed_value = value()?.parse()?,
+            _ if arg.starts_with('-') => {
+                return Err(Error::msg(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => rest.push(arg),
+        }
+    }
+    Ok((seed_value, scale, rest))
+}
+
+fn generate_input(args: impl Iterator<Item = String>) -> Result<()> {
+    let (seed, scale, args) = generator_args(args, "--seed", 0)?;
+    if args.len() > 1 {
+        return Err(Error::msg(format!("too many arguments\n\n{USAGE}")));
+    }
+    let day = parse_day(args.first())?;
+    print!("{}", generate(day, seed, scale)?);
+    Ok(())
+}
+
+fn fuzz_days(args: impl Iterator<Item = String>) -> Result<()> {
+    let (seeds, scale, mut days) = generator_args(args, "--seeds", 100)?;
+    if days.is_empty() {
+        days.push("all".to_owned());
+    }
+    for puzzle in select_puzzles(days, &InputSource::File)? {
+        fuzz(puzzle, 0..seeds, scale).with_context(|| {
+            format!(
+                "run `aoc generate --seed SEED --scale {scale} {}` to get the failing input",
+                puzzle.day()
+            )
+        })?;
+        println!("Day {}: {seeds} inputs ok", puzzle.day());
+    }
+    Ok(())
+}
+
+/// Splits the arguments of the subcommands working on a single day into the year and the
+/// positional arguments.
+fn year_args(mut args: impl Iterator<Item = String>) -> Result<(u16, Vec<String>)> {
+    let mut year = default_year();
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => {
+                let value = args
+                    .next()
+                    .with_context(|| format!("missing value for --year\n\n{USAGE}"))?;
+                year = value
+                    .parse()
+                    .with_context(|| format!("invalid year {value:?}"))?;
+            }
+            _ if arg.starts_with('-') => {
+                return Err(Error::msg(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => rest.push(arg),
+        }
+    }
+    Ok((year, rest))
+}
+
+fn parse_day(arg: Option<&String>) -> Result<u8> {
+    let arg = arg.with_context(|| format!("missing day\n\n{USAGE}"))?;
+    arg.parse().with_context(|| format!("invalid day {arg:?}"))
+}
+
+fn new(args: impl Iterator<Item = String>) -> Result<()> {
+    let (year, args) = year_args(args)?;
+    if args.len() > 1 {
+        return Err(Error::msg(format!("too many arguments\n\n{USAGE}")));
+    }
+    let day = match args.first() {
+        Some(_) => parse_day(args.first())?,
+        None => current_day(),
+    };
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    println!("Year {year}, day {day}");
+    for path in new_day(root, day, year)? {
+        println!(
+            "  wrote {}",
+            path.strip_prefix(root).unwrap_or(&path).display()
+        );
+    }
+    println!(
+        "Run `aoc download {day}` or save the puzzle input as inputs/day{day:02}, and paste the \
+         example into inputs/day{day:02}.example1."
+    );
+    Ok(())
+}
+
+fn download(args: impl Iterator<Item = String>) -> Result<()> {
+    let (year, args) = year_args(args)?;
+    if args.len() > 1 {
+        return Err(Error::msg(format!("too many arguments\n\n{USAGE}")));
+    }
+    let day = parse_day(args.first())?;
+    let path = input_path(&format!("day{day:02}"));
+    Client::from_env(year)?.download_input(day, &path)?;
+    println!(
+        "Saved the input for day {day} of {year} as {}",
+        path.display()
+    );
+    Ok(())
+}
+
+fn submit(args: impl Iterator<Item = String>) -> Result<()> {
+    let (year, args) = year_args(args)?;
+    if !(2..=3).contains(&args.len()) {
+        return Err(Error::msg(format!("expected DAY PART [ANSWER]\n\n{USAGE}")));
+    }
+    let day = parse_day(args.first())?;
+    let part = match args[1].as_str() {
+        "1" => 1,
+        "2" => 2,
+        part => return Err(Error::msg(format!("invalid part {part:?}"))),
+    };
+    let path = input_path(&format!("day{day:02}"));
+    let answer = match args.get(2) {
+        Some(answer) => answer.clone(),
+        None => {
+            let puzzle = puzzle(day)?;
+            let input = InputSource::File.read(&puzzle.name())?;
+            let answer = puzzle.solve(&input)?[usize::from(part - 1)].clone();
+            println!("Day {day}, part {part}: {answer}");
+            answer
+        }
+    };
+    if answer.contains('\n') {
+        return Err(Error::msg(
+            "multi-line answers have to be read and submitted by hand",
+        ));
+    }
+    let verdict =
+        Client::from_env(year)?.submit_checked(day, part, &answer, &guesses_path(&path))?;
+    println!("{verdict}");
+    Ok(())
+}
@@ -1,14 +1,69 @@
-use anyhow::Result;
-use aoc2022::{parse_lines, read_input};
+use anyhow::{Context, Error, Result};
+use aoc2022::{
+    day01::{read_report, solve_reader, Day01},
+    locate_parse_error, InputSource, Solution, INPUT_OPTIONS_USAGE,
+};
+
+const USAGE: &str = "usage: day01 [INPUT OPTIONS] [--top N] [--stream]
+
+Prints the answers to both parts, or with --top N, the N elves carrying the
+most calories, ranked by their calories and then by their position in the input.
+With --stream, the input is read a line at a time instead of all at once, so
+that inputs of any size can be solved in constant memory.";
 
 fn main() -> Result<()> {
-    let input = read_input!()?;
-    let mut calories: Vec<u32> = input
-        .split("\n\n")
-        .map(|elf| parse_lines::<u32>(elf).sum::<Result<u32>>())
-        .collect::<Result<_>>()?;
-    calories.sort_unstable();
-    println!("{}", calories.last().unwrap());
-    println!("{}", calories[calories.len() - 3..].iter().sum::<u32>());
+    let (source, args) = InputSource::from_args(std::env::args().skip(1))?;
+    let mut top = None;
+    let mut stream = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}\n\n{INPUT_OPTIONS_USAGE}");
+                return Ok(());
+            }
+            "--top" => {
+                let value = args
+                    .next()
+                    .with_context(|| format!("missing value for --top\n\n{USAGE}"))?;
+                top = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid number of elves {value:?}"))?,
+                );
+            }
+            "--stream" => stream = true,
+            _ => {
+                return Err(Error::msg(format!(
+                    "unexpected argument {arg:?}\n\n{USAGE}"
+                )))
+            }
+        }
+    }
+    let name = env!("CARGO_BIN_NAME");
+    if stream {
+        let reader = source.reader(name)?;
+        match top {
+            Some(k) => println!("{}", read_report(reader, k)?),
+            None => {
+                let (part1, part2) = solve_reader(reader)?;
+                println!("{part1}");
+                println!("{part2}");
+            }
+        }
+        return Ok(());
+    }
+    let input = source.read(name)?;
+    match top {
+        Some(k) => {
+            let inventory = Day01::parse(&input).map_err(|e| locate_parse_error(&input, e))?;
+            println!("{}", inventory.report(k)?);
+        }
+        None => {
+            let (part1, part2) = Day01::solve(&input)?;
+            println!("{part1}");
+            println!("{part2}");
+        }
+    }
     Ok(())
 }
@@ -1,89 +1,76 @@
 use anyhow::{Context, Error, Result};
-use aoc2022::read_input;
+use aoc2022::{
+    day02::{Day02, Decoding, Rules},
+    locate_parse_error, InputSource, Solution, INPUT_OPTIONS_USAGE,
+};
+use std::fs;
 
-fn main() -> Result<()> {
-    let input = read_input!()?;
-    let rounds = parse(&input)?;
-    println!("{}", total_score(&rounds, strategy1)?);
-    println!("{}", total_score(&rounds, strategy2)?);
-    Ok(())
-}
-
-fn total_score<F>(rounds: &[(char, char)], strategy: F) -> Result<i32>
-where
-    F: Fn(Shape, char) -> Result<Shape>,
-{
-    rounds
-        .iter()
-        .map(|&(c, d)| {
-            let a = Shape::from_code(c, "ABC")?;
-            let b = strategy(a, d)?;
-            Ok(round_score(a, b))
-        })
-        .sum()
-}
+const USAGE: &str = "usage: day02 [INPUT OPTIONS] [--rules PATH] [--search]
 
-fn strategy1(_: Shape, d: char) -> Result<Shape> {
-    Shape::from_code(d, "XYZ")
-}
+Prints the scores of the strategy guide when its second column names shapes
+and when it names outcomes. With --rules PATH, the game is played by the rules
+in PATH instead of those of rock paper scissors, e.g.
 
-fn strategy2(a: Shape, d: char) -> Result<Shape> {
-    let b = match d {
-        'X' => Shape::try_from((a as i32 + 2) % 3).unwrap(),
-        'Y' => a,
-        'Z' => Shape::try_from((a as i32 + 1) % 3).unwrap(),
-        _ => return Err(Error::msg("invalid strategy")),
-    };
-    Ok(b)
-}
+    # Rock paper scissors lizard Spock
+    shapes: Rock Spock Paper Lizard Scissors
+    shape points: 1 2 3 4 5
+    outcome points: 0 3 6
+    opponent: ABCDE
+    response: VWXYZ
 
-fn round_score(a: Shape, b: Shape) -> i32 {
-    let score = match b as i32 - a as i32 {
-        0 => 3,
-        1 | -2 => 6,
-        2 | -1 => 0,
-        _ => unreachable!(),
-    };
-    score + b as i32 + 1
-}
+Each shape beats the shapes up to halfway back around the cycle.
 
-#[derive(Clone, Copy, Debug)]
-#[repr(i32)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissors,
-}
+With --search, the guide is scored under every mapping of the response codes
+to shapes, and to outcomes, and the best and worst mappings are printed with
+the number of mappings reaching each score.";
 
-impl Shape {
-    fn from_code(c: char, code: &str) -> Result<Shape> {
-        let index = code.chars().position(|d| c == d).context("invalid code")?;
-        Shape::try_from(index as i32)
-    }
-}
-
-impl TryFrom<i32> for Shape {
-    type Error = Error;
-
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            0 => Ok(Shape::Rock),
-            1 => Ok(Shape::Paper),
-            2 => Ok(Shape::Scissors),
-            _ => Err(Error::msg("invalid index")),
+fn main() -> Result<()> {
+    let (source, args) = InputSource::from_args(std::env::args().skip(1))?;
+    let mut rules = None;
+    let mut search = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}\n\n{INPUT_OPTIONS_USAGE}");
+                return Ok(());
+            }
+            "--rules" => {
+                let path = args
+                    .next()
+                    .with_context(|| format!("missing value for --rules\n\n{USAGE}"))?;
+                let text =
+                    fs::read_to_string(&path).with_context(|| format!("failed to read {path}"))?;
+                let parsed: Rules = text
+                    .parse()
+                    .map_err(|e| locate_parse_error(&text, e))
+                    .with_context(|| format!("invalid rules in {path}"))?;
+                rules = Some(parsed);
+            }
+            "--search" => search = true,
+            _ => {
+                return Err(Error::msg(format!(
+                    "unexpected argument {arg:?}\n\n{USAGE}"
+                )))
+            }
         }
     }
-}
-
-fn parse(input: &str) -> Result<Vec<(char, char)>> {
-    input
-        .lines()
-        .map(|line| {
-            let mut chars = line.chars();
-            Ok((
-                chars.next().context("line too short")?,
-                chars.nth(1).context("line too short")?,
-            ))
-        })
-        .collect()
+    let input = source.read(env!("CARGO_BIN_NAME"))?;
+    if rules.is_none() && !search {
+        let (part1, part2) = Day02::solve(&input)?;
+        println!("{part1}");
+        println!("{part2}");
+        return Ok(());
+    }
+    let rules = rules.unwrap_or_default();
+    let rounds = Day02::parse(&input).map_err(|e| locate_parse_error(&input, e))?;
+    if search {
+        println!("{}", rules.search(&rounds, Decoding::Shape)?);
+        println!();
+        println!("{}", rules.search(&rounds, Decoding::Outcome)?);
+    } else {
+        println!("{}", rules.total_score(&rounds, Decoding::Shape)?);
+        println!("{}", rules.total_score(&rounds, Decoding::Outcome)?);
+    }
+    Ok(())
 }
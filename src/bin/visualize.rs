@@ -0,0 +1,62 @@
+use anyhow::{Context, Error, Result};
+use aoc2022::{
+    animation::{animation, Player, CONTROLS},
+    InputSource, INPUT_OPTIONS_USAGE,
+};
+
+const USAGE: &str = "usage: visualize [INPUT OPTIONS] [--fps N] [--skip N] [--paused] DAY [PART]
+
+Plays the simulation of part 1 or 2 (default 1) of days 5, 9, 12 and 14 in the
+terminal, drawing N frames per second (default 20). With --skip N, N frames are
+skipped between two frames that are drawn.
+
+Keys while playing:";
+
+fn main() -> Result<()> {
+    let (source, args) = InputSource::from_args(std::env::args().skip(1))?;
+    // Standard input is used for the controls, so it is only read when asked to.
+    let source = match source {
+        InputSource::Auto => InputSource::File,
+        source => source,
+    };
+    let mut args = args.into_iter();
+    let mut player = Player::default();
+    let mut positional = vec![];
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("missing value for {arg}\n\n{USAGE}"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}\n  {CONTROLS}\n\n{INPUT_OPTIONS_USAGE}");
+                return Ok(());
+            }
+            "--fps" => {
+                player.fps = value()?.parse()?;
+                if !player.fps.is_finite() || player.fps <= 0.0 {
+                    return Err(Error::msg("the frame rate must be positive"));
+                }
+            }
+            "--skip" => player.steps_per_frame = value()?.parse::<usize>()? + 1,
+            "--paused" => player.paused = true,
+            _ if arg.starts_with('-') => {
+                return Err(Error::msg(format!("unknown option {arg}\n\n{USAGE}")))
+            }
+            _ => positional.push(arg),
+        }
+    }
+    let (day, part) = match positional.as_slice() {
+        [day] => (day, "1"),
+        [day, part] => (day, part.as_str()),
+        _ => return Err(Error::msg(format!("expected DAY [PART]\n\n{USAGE}"))),
+    };
+    let day: u8 = day
+        .parse()
+        .with_context(|| format!("invalid day {day:?}"))?;
+    let part: u8 = part
+        .parse()
+        .with_context(|| format!("invalid part {part:?}"))?;
+    let input = source.read(&format!("day{day:02}"))?;
+    player.play(&mut *animation(day, part, &input)?)
+}
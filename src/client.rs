@@ -0,0 +1,451 @@
+//! Downloading puzzle inputs and submitting answers to the Advent of Code website.
+//!
+//! Requests are authenticated with the session cookie of a logged in browser, which is read from
+//! `$ADVENT_OF_CODE_SESSION` or from `~/.adventofcode.session`, the same places other Advent of
+//! Code tools look for it. Every submitted answer is recorded in `inputs/dayNN.guesses`, so that
+//! wrong answers are rejected locally instead of being submitted again.
+
+use anyhow::{Context, Error, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The environment variable containing the session token.
+pub const SESSION_VAR: &str = "ADVENT_OF_CODE_SESSION";
+
+/// The environment variable overriding the URL of the website, e.g. for testing.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// The file containing the session token if `$ADVENT_OF_CODE_SESSION` is not set.
+pub fn session_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(Path::new(&home).join(".adventofcode.session"))
+}
+
+pub fn read_session() -> Result<String> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        return Ok(session.trim().to_owned());
+    }
+    let path = session_path().context("cannot locate the session file without $HOME")?;
+    let session = fs::read_to_string(&path).with_context(|| {
+        format!(
+            "failed to read the session token from ${SESSION_VAR} or {}",
+            path.display()
+        )
+    })?;
+    Ok(session.trim().to_owned())
+}
+
+/// The website's response to a submitted answer.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Another answer was submitted too recently, with the remaining time if stated.
+    RateLimited {
+        wait: Option<String>,
+    },
+    /// The part was already solved, or is not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Whether the answer was rejected as wrong, as opposed to not being checked at all.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "right answer"),
+            Verdict::Wrong => write!(f, "wrong answer"),
+            Verdict::TooHigh => write!(f, "wrong answer, too high"),
+            Verdict::TooLow => write!(f, "wrong answer, too low"),
+            Verdict::RateLimited { wait: Some(wait) } => write!(f, "rate limited, {wait} left"),
+            Verdict::RateLimited { wait: None } => write!(f, "rate limited"),
+            Verdict::WrongLevel => write!(f, "part already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// Extracts the verdict from the HTML page returned for a submitted answer.
+pub fn parse_verdict(html: &str) -> Result<Verdict> {
+    lazy_static! {
+        static ref WAIT: Regex = Regex::new(r"You have (.+?) left to wait").unwrap();
+    }
+    let verdict = if html.contains("That's the right answer") {
+        Verdict::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        let wait = WAIT.captures(html).map(|c| c[1].to_owned());
+        Verdict::RateLimited { wait }
+    } else if html.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        return Err(Error::msg("unexpected response to submitted answer"));
+    };
+    Ok(verdict)
+}
+
+/// A submitted answer and the verdict it received.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Guess {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// All answers submitted for a puzzle that were checked.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Guesses(pub Vec<Guess>);
+
+impl Guesses {
+    /// Loads the guesses from `path`, which may not exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("invalid guesses {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)? + "\n";
+        fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Returns an error if `answer` is known to be wrong, either because it was submitted
+    /// before, or because it lies outside the bounds given by earlier numeric guesses.
+    pub fn check(&self, part: u8, answer: &str) -> Result<()> {
+        let number = answer.parse::<i128>().ok();
+        for guess in self
+            .0
+            .iter()
+            .filter(|g| g.part == part && g.verdict.is_wrong())
+        {
+            let excluded = guess.answer == answer
+                || match (number, guess.answer.parse::<i128>(), &guess.verdict) {
+                    (Some(n), Ok(bound), Verdict::TooHigh) => n >= bound,
+                    (Some(n), Ok(bound), Verdict::TooLow) => n <= bound,
+                    _ => false,
+                };
+            if excluded {
+                return Err(Error::msg(format!(
+                    "{answer} is known to be wrong, {} was a {}",
+                    guess.answer, guess.verdict
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The answer for `part` that was accepted as correct, if any.
+    pub fn correct(&self, part: u8) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|g| g.part == part && g.verdict == Verdict::Correct)
+            .map(|g| g.answer.as_str())
+    }
+
+    /// Records a verdict, unless the answer was not checked at all.
+    pub fn record(&mut self, part: u8, answer: &str, verdict: &Verdict) {
+        if verdict.is_wrong() || *verdict == Verdict::Correct {
+            self.0.push(Guess {
+                part,
+                answer: answer.to_owned(),
+                verdict: verdict.clone(),
+            });
+        }
+    }
+}
+
+/// The path of the guesses recorded for the input file at `input_path`.
+pub fn guesses_path(input_path: &Path) -> PathBuf {
+    let mut path = input_path.as_os_str().to_owned();
+    path.push(".guesses");
+    path.into()
+}
+
+/// A client for the puzzles of one year.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build();
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            year,
+        }
+    }
+
+    /// Creates a client with the configured session token and website URL.
+    pub fn from_env(year: u16) -> Result<Self> {
+        let base_url = std::env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &read_session()?, year))
+    }
+
+    fn url(&self, day: u8, path: &str) -> String {
+        format!("{}/{}/day/{day}{path}", self.base_url, self.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetches the puzzle input for `day`.
+    pub fn input(&self, day: u8) -> Result<String> {
+        let url = self.url(day, "/input");
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|e| request_error(&url, e))?;
+        Ok(response.into_string()?)
+    }
+
+    /// Downloads the puzzle input for `day` to `path`, which must not exist yet.
+    pub fn download_input(&self, day: u8, path: &Path) -> Result<()> {
+        if path.exists() {
+            return Err(Error::msg(format!("{} already exists", path.display())));
+        }
+        let input = self.input(day)?;
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(input.as_bytes()))
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    /// Submits an answer without checking or recording it.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Verdict> {
+        let url = self.url(day, "/answer");
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error(&url, e))?;
+        parse_verdict(&response.into_string()?)
+    }
+
+    /// Submits an answer unless it is known to be wrong from the guesses recorded in
+    /// `guesses_path`, and records the verdict.
+    pub fn submit_checked(
+        &self,
+        day: u8,
+        part: u8,
+        answer: &str,
+        guesses_path: &Path,
+    ) -> Result<Verdict> {
+        let mut guesses = Guesses::load(guesses_path)?;
+        match guesses.correct(part) {
+            Some(correct) if correct == answer => return Ok(Verdict::Correct),
+            Some(correct) => {
+                return Err(Error::msg(format!(
+                    "part {part} was already solved with {correct}"
+                )))
+            }
+            None => guesses.check(part, answer)?,
+        }
+        let verdict = self.submit(day, part, answer)?;
+        guesses.record(part, answer, &verdict);
+        guesses.save(guesses_path)?;
+        Ok(verdict)
+    }
+}
+
+fn request_error(url: &str, error: ureq::Error) -> Error {
+    match error {
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            Error::msg(format!(
+                "request to {url} failed with status {status}: {}",
+                body.trim()
+            ))
+        }
+        e => Error::new(e).context(format!("request to {url} failed")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::ScratchDir;
+    use std::{sync::mpsc, thread};
+
+    /// A request as received by the mock server.
+    #[derive(Debug)]
+    struct Request {
+        method: String,
+        url: String,
+        cookie: Option<String>,
+        body: String,
+    }
+
+    /// Starts a server on a free local port that answers the given number of requests with the
+    /// given status codes and bodies, and returns its URL and the received requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Request>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let mut request = server.recv().unwrap();
+                let mut content = String::new();
+                request.as_reader().read_to_string(&mut content).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+                tx.send(Request {
+                    method: request.method().to_string(),
+                    url: request.url().to_owned(),
+                    cookie,
+                    body: content,
+                })
+                .unwrap();
+                let response = tiny_http::Response::from_string(body).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    const RIGHT: &str = "<p>That's the right answer! You are one gold star closer.</p>";
+    const TOO_HIGH: &str = "<p>That's not the right answer; your answer is too high.</p>";
+
+    #[test]
+    fn verdicts() {
+        let wrong = "<p>That's not the right answer. If you're stuck, ...</p>";
+        let too_low = "<p>That's not the right answer; your answer is too low.</p>";
+        let rate_limited = "<p>You gave an answer too recently; you have to wait after \
+                            submitting an answer before trying again.  You have 37s left to \
+                            wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p>";
+        let wrong_level = "<p>You don't seem to be solving the right level.</p>";
+        assert_eq!(parse_verdict(RIGHT).unwrap(), Verdict::Correct);
+        assert_eq!(parse_verdict(wrong).unwrap(), Verdict::Wrong);
+        assert_eq!(parse_verdict(TOO_HIGH).unwrap(), Verdict::TooHigh);
+        assert_eq!(parse_verdict(too_low).unwrap(), Verdict::TooLow);
+        assert_eq!(
+            parse_verdict(rate_limited).unwrap(),
+            Verdict::RateLimited {
+                wait: Some("37s".into())
+            }
+        );
+        assert_eq!(parse_verdict(wrong_level).unwrap(), Verdict::WrongLevel);
+        assert!(parse_verdict("<html></html>").is_err());
+    }
+
+    #[test]
+    fn download() {
+        let (url, requests) = serve(vec![(200, "1\n2\n"), (400, "Please log in.")]);
+        let client = Client::new(&url, "abc", 2022);
+        let dir = ScratchDir::new("client-download");
+        let path = dir.join("day07");
+        client.download_input(7, &path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.url, "/2022/day/7/input");
+        assert_eq!(request.cookie.as_deref(), Some("session=abc"));
+
+        // Existing inputs are never overwritten, so no request is made.
+        assert!(client.download_input(7, &path).is_err());
+        fs::remove_file(&path).unwrap();
+        let err = client.download_input(7, &path).unwrap_err();
+        assert!(err.to_string().contains("status 400: Please log in."));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn submit() {
+        let (url, requests) = serve(vec![(200, RIGHT)]);
+        let client = Client::new(&url, "abc", 2022);
+        assert_eq!(client.submit(3, 2, "42").unwrap(), Verdict::Correct);
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "/2022/day/3/answer");
+        assert_eq!(request.cookie.as_deref(), Some("session=abc"));
+        assert_eq!(request.body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn wrong_guesses_are_not_resubmitted() {
+        let (url, requests) = serve(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let client = Client::new(&url, "abc", 2022);
+        let dir = ScratchDir::new("client-guesses");
+        let path = dir.join("day01.guesses");
+        let verdict = client.submit_checked(1, 1, "100", &path).unwrap();
+        assert_eq!(verdict, Verdict::TooHigh);
+        assert!(client.submit_checked(1, 1, "100", &path).is_err());
+        assert!(client.submit_checked(1, 1, "150", &path).is_err());
+        assert_eq!(
+            client.submit_checked(1, 1, "99", &path).unwrap(),
+            Verdict::Correct
+        );
+        // Correct answers are answered locally as well.
+        assert_eq!(
+            client.submit_checked(1, 1, "99", &path).unwrap(),
+            Verdict::Correct
+        );
+        assert!(client.submit_checked(1, 1, "98", &path).is_err());
+        assert_eq!(requests.try_iter().count(), 2);
+        let guesses = Guesses::load(&path).unwrap();
+        assert_eq!(guesses.0.len(), 2);
+        assert_eq!(guesses.correct(1), Some("99"));
+    }
+
+    #[test]
+    fn guess_bounds() {
+        let mut guesses = Guesses::default();
+        guesses.record(1, "10", &Verdict::TooLow);
+        guesses.record(1, "20", &Verdict::TooHigh);
+        guesses.record(1, "abc", &Verdict::Wrong);
+        guesses.record(1, "15", &Verdict::RateLimited { wait: None });
+        assert_eq!(guesses.0.len(), 3);
+        assert!(guesses.check(1, "10").is_err());
+        assert!(guesses.check(1, "5").is_err());
+        assert!(guesses.check(1, "25").is_err());
+        assert!(guesses.check(1, "abc").is_err());
+        assert!(guesses.check(1, "15").is_ok());
+        assert!(guesses.check(1, "abd").is_ok());
+        assert!(guesses.check(2, "10").is_ok());
+    }
+}
@@ -0,0 +1,393 @@
+//! Day 1: Calorie Counting
+
+use crate::{parse_blocks_with, parse_lines, BlockLine, BlockLines, Solution};
+use anyhow::{Context, Error, Result};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt::Display,
+    io::BufRead,
+    str::FromStr,
+};
+
+/// Totals the calories carried by each elf.
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed = Inventory;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(inventory: &Self::Parsed) -> Result<u64> {
+        inventory.report(1)?.best()
+    }
+
+    fn part2(inventory: &Self::Parsed) -> Result<u64> {
+        inventory.report(3)?.total(3)
+    }
+}
+
+/// Solves both parts while reading the input from `reader`, see [`read_report`].
+pub fn solve_reader(reader: impl BufRead) -> Result<(u64, u64)> {
+    let report = read_report(reader, 3)?;
+    Ok((report.best()?, report.total(3)?))
+}
+
+/// Reads an inventory from `reader` a line at a time and ranks the `k` elves carrying the most
+/// calories.
+///
+/// Only the total of the current elf and the best `k` elves are kept, so the memory used does not
+/// grow with the input. Blocks are split like [`blocks`](crate::blocks) does.
+pub fn read_report(mut reader: impl BufRead, k: usize) -> Result<Report> {
+    let mut top = TopK::new(k);
+    let mut elves = 0;
+    let mut finish = |current: &mut Option<u64>| {
+        if let Some(calories) = current.take() {
+            elves += 1;
+            top.push(Elf {
+                number: elves,
+                calories,
+            });
+        }
+    };
+    let mut block_lines = BlockLines::default();
+    let mut current = None;
+    let mut line = String::new();
+    for number in 1.. {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .with_context(|| format!("failed to read line {number}"))?;
+        if read == 0 {
+            break;
+        }
+        let kind = block_lines.classify(&line);
+        if kind == BlockLine::Blank {
+            finish(&mut current);
+            continue;
+        }
+        let text = line.trim();
+        let calories: u64 = text
+            .parse()
+            .with_context(|| format!("invalid calories at line {number}: {text:?}"))?;
+        if kind == BlockLine::First {
+            finish(&mut current);
+        }
+        let total = current.unwrap_or(0u64).checked_add(calories);
+        current =
+            Some(total.with_context(|| format!("total calories too large at line {number}"))?);
+    }
+    finish(&mut current);
+    Report::new(top.into_sorted_vec(), elves)
+}
+
+/// An elf and the total calories it carries.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Elf {
+    /// The position of the elf in the input, starting at 1.
+    pub number: usize,
+    pub calories: u64,
+}
+
+/// The total calories carried by each elf, in the order of the input.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Inventory {
+    totals: Vec<u64>,
+}
+
+impl Inventory {
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    pub fn elves(&self) -> impl Iterator<Item = Elf> + '_ {
+        (1..)
+            .zip(&self.totals)
+            .map(|(number, &calories)| Elf { number, calories })
+    }
+
+    /// The `k` elves carrying the most calories, best first, see [`TopK`].
+    pub fn top(&self, k: usize) -> Vec<Elf> {
+        let mut top = TopK::new(k);
+        self.elves().for_each(|elf| top.push(elf));
+        top.into_sorted_vec()
+    }
+
+    /// Ranks the `k` elves carrying the most calories.
+    pub fn report(&self, k: usize) -> Result<Report> {
+        Report::new(self.top(k), self.len())
+    }
+}
+
+impl FromStr for Inventory {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let totals = parse_blocks_with(input, |elf| checked_sum(parse_lines(elf.text)))
+            .collect::<Result<_>>()?;
+        Ok(Self { totals })
+    }
+}
+
+/// Sums calories, failing instead of overflowing.
+fn checked_sum(mut calories: impl Iterator<Item = Result<u64>>) -> Result<u64> {
+    calories.try_fold(0u64, |total, c| {
+        total.checked_add(c?).context("total calories too large")
+    })
+}
+
+/// The `k` elves carrying the most calories among those pushed so far.
+///
+/// The elves are kept in a min-heap of at most `k` elves, so selecting them from `n` elves takes
+/// O(n log k) time, which is linear for a fixed `k`, and O(min(k, n)) memory. Elves carrying the
+/// same calories are ranked by their numbers, so the first of them in the input ranks highest.
+#[derive(Clone, Debug)]
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<Rank>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    pub fn push(&mut self, elf: Elf) {
+        let rank = Rank(elf);
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(rank));
+        } else if let Some(mut lowest) = self.heap.peek_mut() {
+            if rank > lowest.0 {
+                *lowest = Reverse(rank);
+            }
+        }
+    }
+
+    /// The elves, best first.
+    pub fn into_sorted_vec(self) -> Vec<Elf> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(Rank(elf))| elf)
+            .collect()
+    }
+}
+
+/// Orders elves by calories, and elves with the same calories by their numbers in reverse, so
+/// that the better ranked of two elves is the greater.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Rank(Elf);
+
+impl PartialOrd for Rank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.calories, Reverse(self.0.number)).cmp(&(other.0.calories, Reverse(other.0.number)))
+    }
+}
+
+/// A ranking of the elves carrying the most calories, along with the running total of the
+/// calories carried by the elves ranked so far.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    /// The ranked elves, best first, with the running totals.
+    pub rows: Vec<(Elf, u64)>,
+    /// The number of elves in the inventory, including those not ranked.
+    pub elves: usize,
+}
+
+impl Report {
+    /// Makes a report from ranked elves, best first.
+    pub fn new(top: Vec<Elf>, elves: usize) -> Result<Self> {
+        let mut total = 0u64;
+        let rows = top
+            .into_iter()
+            .map(|elf| {
+                total = total
+                    .checked_add(elf.calories)
+                    .context("total calories too large")?;
+                Ok((elf, total))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rows, elves })
+    }
+
+    /// The calories carried by the best elf.
+    pub fn best(&self) -> Result<u64> {
+        let (elf, _) = self.rows.first().context("no elves in input")?;
+        Ok(elf.calories)
+    }
+
+    /// The calories carried by the best `n` elves together.
+    pub fn total(&self, n: usize) -> Result<u64> {
+        if n == 0 {
+            return Ok(0);
+        }
+        let (_, total) = self
+            .rows
+            .get(n - 1)
+            .with_context(|| format!("expected at least {n} elves, found {}", self.elves))?;
+        Ok(*total)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = ["Rank", "Elf", "Calories", "Total"];
+        let rows: Vec<[String; 4]> = (1..)
+            .zip(&self.rows)
+            .map(|(rank, (elf, total)): (usize, _)| {
+                [
+                    rank.to_string(),
+                    elf.number.to_string(),
+                    elf.calories.to_string(),
+                    total.to_string(),
+                ]
+            })
+            .collect();
+        let widths: [usize; 4] = std::array::from_fn(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .fold(header[i].len(), usize::max)
+        });
+        let line = |cells: [&str; 4]| -> String {
+            let cells: Vec<String> = (0..4)
+                .map(|i| format!("{:>width$}", cells[i], width = widths[i]))
+                .collect();
+            cells.join("  ")
+        };
+        writeln!(f, "{}", line(header))?;
+        for row in &rows {
+            writeln!(f, "{}", line(row.each_ref().map(String::as_str)))?;
+        }
+        write!(f, "{} of {} elves", self.rows.len(), self.elves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::generate;
+
+    fn elf(number: usize, calories: u64) -> Elf {
+        Elf { number, calories }
+    }
+
+    #[test]
+    fn top_k() {
+        let inventory: Inventory = "1\n\n5\n\n3\n\n5\n\n2\n\n3\n".parse().unwrap();
+        assert_eq!(inventory.len(), 6);
+        assert_eq!(inventory.top(0), []);
+        assert_eq!(inventory.top(1), [elf(2, 5)]);
+        // Ties are ranked by the position in the input.
+        assert_eq!(
+            inventory.top(4),
+            [elf(2, 5), elf(4, 5), elf(3, 3), elf(6, 3)]
+        );
+        let all = inventory.top(10);
+        assert_eq!(all.len(), 6);
+        let mut sorted: Vec<Elf> = inventory.elves().collect();
+        sorted.sort_by_key(|elf| (Reverse(elf.calories), elf.number));
+        assert_eq!(all, sorted);
+        // The heap only grows with the elves pushed.
+        assert_eq!(inventory.top(usize::MAX), sorted);
+    }
+
+    #[test]
+    fn report() {
+        let inventory: Inventory = "1000\n2000\n\n100000\n\n300\n".parse().unwrap();
+        assert_eq!(
+            inventory.report(2).unwrap().to_string(),
+            "Rank  Elf  Calories   Total\n   \
+                1    2    100000  100000\n   \
+                2    1      3000  103000\n\
+             2 of 3 elves"
+        );
+        assert_eq!(
+            Inventory::default().report(3).unwrap().to_string(),
+            "Rank  Elf  Calories  Total\n0 of 0 elves"
+        );
+    }
+
+    #[test]
+    fn streaming() {
+        for input in [
+            "1\n2\n\n3\n".to_owned(),
+            "\n \n1\r\n2\r\n\r\n\r\n5\r\n  \n4".to_owned(),
+            generate(1, 7, 50).unwrap(),
+        ] {
+            let inventory: Inventory = input.parse().unwrap();
+            assert_eq!(
+                read_report(input.as_bytes(), 5).unwrap(),
+                inventory.report(5).unwrap()
+            );
+        }
+        let input = generate(1, 3, 20).unwrap();
+        assert_eq!(
+            solve_reader(input.as_bytes()).unwrap(),
+            Day01::solve(&input).unwrap()
+        );
+
+        let error = read_report("1\n\n2\nx\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(error.to_string(), "invalid calories at line 4: \"x\"");
+        let error = solve_reader("1\n\n2\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "expected at least 3 elves, found 2");
+    }
+
+    /// An inventory of 100000 elves, made up while it is being read.
+    struct Elves {
+        elf: u64,
+        pending: Vec<u8>,
+    }
+
+    impl std::io::Read for Elves {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pending.is_empty() && self.elf < 100_000 {
+                self.elf += 1;
+                self.pending = format!("{}\n{}\n\n", self.elf % 1000, self.elf % 7).into_bytes();
+            }
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn streaming_large_input() {
+        let elves = Elves {
+            elf: 0,
+            pending: vec![],
+        };
+        let report = read_report(std::io::BufReader::new(elves), 2).unwrap();
+        assert_eq!(report.elves, 100_000);
+        // Elf 6999 is the first to carry 999 + 6 calories, and every 7000th elf after it as well.
+        assert_eq!(
+            report.rows,
+            [(elf(6999, 1005), 1005), (elf(13999, 1005), 2010)]
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let max = u64::MAX;
+        assert!(format!("{max}\n1").parse::<Inventory>().is_err());
+        let inventory: Inventory = format!("{max}\n\n1").parse().unwrap();
+        assert!(inventory.report(2).is_err());
+    }
+}
@@ -0,0 +1,586 @@
+//! Day 2: Rock Paper Scissors
+//!
+//! The game is played by [`Rules`], which allow any odd number of shapes arranged in a cycle,
+//! e.g. rock paper scissors lizard Spock, as well as other codes and points.
+
+use crate::{parse_at, parse_lines_with, Solution};
+use anyhow::{Context, Error, Result};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::HashSet,
+    fmt::Display,
+    str::FromStr,
+};
+
+/// Scores a rock-paper-scissors strategy guide under both decodings of its second column.
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<(char, char)>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse(input)
+    }
+
+    fn part1(rounds: &Self::Parsed) -> Result<i64> {
+        Rules::default().total_score(rounds, Decoding::Shape)
+    }
+
+    fn part2(rounds: &Self::Parsed) -> Result<i64> {
+        Rules::default().total_score(rounds, Decoding::Outcome)
+    }
+}
+
+/// The rules of the puzzle, in the format read by [`Rules::from_str`].
+pub const STANDARD_RULES: &str = "\
+shapes: Rock Paper Scissors
+shape points: 1 2 3
+outcome points: 0 3 6
+opponent: ABC
+response: XYZ
+";
+
+/// How the response column of a strategy guide is read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Decoding {
+    /// The response names the shape to play.
+    Shape,
+    /// The response names the outcome of the round, see [`Rules::respond`].
+    Outcome,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+/// The rules of a game of shapes arranged in a cycle, such as rock paper scissors.
+///
+/// Shapes are numbered by their position in the cycle. With `n` shapes, each shape beats the
+/// `(n - 1) / 2` shapes before it and loses to the `(n - 1) / 2` shapes after it, so `n` must be
+/// odd. Rock paper scissors lizard Spock is played with the cycle `Rock Spock Paper Lizard
+/// Scissors`.
+///
+/// Rules are read from lines of `key: value`, where blank lines and lines starting with `#` are
+/// ignored, see [`STANDARD_RULES`]:
+///
+/// - `shapes`: the names of the shapes in the order of the cycle, separated by spaces
+/// - `shape points`: the points for playing each shape
+/// - `outcome points`: the points for a loss, a draw and a win
+/// - `opponent` and `response`: the codes for the shapes in each column of the guide
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    shapes: Vec<String>,
+    shape_points: Vec<i64>,
+    outcome_points: [i64; 3],
+    opponent_codes: Vec<char>,
+    response_codes: Vec<char>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        STANDARD_RULES.parse().unwrap()
+    }
+}
+
+impl Rules {
+    pub fn new(
+        shapes: Vec<String>,
+        shape_points: Vec<i64>,
+        outcome_points: [i64; 3],
+        opponent_codes: Vec<char>,
+        response_codes: Vec<char>,
+    ) -> Result<Self> {
+        let n = shapes.len();
+        if n.is_multiple_of(2) {
+            return Err(Error::msg(format!(
+                "the number of shapes must be odd, found {n}"
+            )));
+        }
+        if shape_points.len() != n {
+            return Err(Error::msg(format!(
+                "expected {n} shape points, found {}",
+                shape_points.len()
+            )));
+        }
+        for (column, codes) in [("opponent", &opponent_codes), ("response", &response_codes)] {
+            if codes.len() != n {
+                return Err(Error::msg(format!(
+                    "expected {n} {column} codes, found {}",
+                    codes.len()
+                )));
+            }
+            let mut seen = HashSet::new();
+            if let Some(code) = codes.iter().find(|&&code| !seen.insert(code)) {
+                return Err(Error::msg(format!("duplicate {column} code {code:?}")));
+            }
+        }
+        Ok(Self {
+            shapes,
+            shape_points,
+            outcome_points,
+            opponent_codes,
+            response_codes,
+        })
+    }
+
+    /// The number of shapes.
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn shape_name(&self, shape: usize) -> &str {
+        &self.shapes[shape]
+    }
+
+    /// The outcome of a round for the player of `b` against `a`.
+    pub fn outcome(&self, a: usize, b: usize) -> Outcome {
+        let n = self.len();
+        match (b + n - a) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// The score of a round for the player of `b` against `a`.
+    pub fn round_score(&self, a: usize, b: usize) -> Result<i64> {
+        let outcome = self.outcome_points[self.outcome(a, b) as usize];
+        outcome
+            .checked_add(self.shape_points[b])
+            .context("round score too large")
+    }
+
+    /// The shape played in response to `a` for the response code at `index`.
+    ///
+    /// Decoded as an outcome, the middle code draws by playing `a` again, and the codes after and
+    /// before it win or lose by playing the shape that many steps further along or back in the
+    /// cycle. With three shapes, the codes are simply loss, draw and win.
+    pub fn respond(&self, decoding: Decoding, a: usize, index: usize) -> usize {
+        let n = self.len();
+        match decoding {
+            Decoding::Shape => index,
+            Decoding::Outcome => (a + n + index - n / 2) % n,
+        }
+    }
+
+    /// The indices of the opponent and response codes of a round.
+    fn code_indices(&self, (c, d): (char, char)) -> Result<(usize, usize)> {
+        Ok((
+            code_index(&self.opponent_codes, c, "opponent")?,
+            code_index(&self.response_codes, d, "response")?,
+        ))
+    }
+
+    /// Scores all `rounds` of a strategy guide, given as pairs of codes.
+    pub fn total_score(&self, rounds: &[(char, char)], decoding: Decoding) -> Result<i64> {
+        rounds
+            .iter()
+            .enumerate()
+            .try_fold(0i64, |total, (i, &round)| {
+                let score = || -> Result<i64> {
+                    let (a, index) = self.code_indices(round)?;
+                    let score = self.round_score(a, self.respond(decoding, a, index))?;
+                    total.checked_add(score).context("total score too large")
+                };
+                score().with_context(|| format!("round {}", i + 1))
+            })
+    }
+
+    /// Scores `rounds` under every decoding of the response codes as shapes or as outcomes, i.e.
+    /// every one-to-one mapping of the codes to the shapes or outcomes used by `decoding`.
+    ///
+    /// There are `n!` mappings for `n` response codes, so at most [`MAX_SEARCH_CODES`] are
+    /// supported.
+    pub fn search(&self, rounds: &[(char, char)], decoding: Decoding) -> Result<Search> {
+        let n = self.len();
+        if n > MAX_SEARCH_CODES {
+            return Err(Error::msg(format!(
+                "cannot search the decodings of {n} response codes, \
+                 at most {MAX_SEARCH_CODES} are supported"
+            )));
+        }
+        // The guide only matters by how often each pair of codes occurs.
+        let mut counts = vec![0i64; n * n];
+        for (i, &round) in rounds.iter().enumerate() {
+            let (a, index) =
+                (self.code_indices(round)).with_context(|| format!("round {}", i + 1))?;
+            counts[a * n + index] += 1;
+        }
+        let mut results = vec![];
+        let mut mapping: Vec<usize> = (0..n).collect();
+        loop {
+            let mut total = 0i64;
+            for (i, &count) in counts.iter().enumerate().filter(|(_, &count)| count > 0) {
+                let a = i / n;
+                let score = self.round_score(a, self.respond(decoding, a, mapping[i % n]))?;
+                total = score
+                    .checked_mul(count)
+                    .and_then(|score| total.checked_add(score))
+                    .context("total score too large")?;
+            }
+            results.push((mapping.clone(), total));
+            if !next_permutation(&mut mapping) {
+                break;
+            }
+        }
+        results.sort_by_key(|&(_, score)| Reverse(score));
+        let labels = (0..n)
+            .map(|i| match decoding {
+                Decoding::Shape => self.shapes[i].clone(),
+                Decoding::Outcome => outcome_label(n, i),
+            })
+            .collect();
+        Ok(Search {
+            decoding,
+            codes: self.response_codes.clone(),
+            labels,
+            results,
+        })
+    }
+}
+
+/// The largest number of response codes [`Rules::search`] tries all decodings of.
+pub const MAX_SEARCH_CODES: usize = 9;
+
+/// The scores of a strategy guide under every decoding of its response codes, see
+/// [`Rules::search`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Search {
+    decoding: Decoding,
+    codes: Vec<char>,
+    /// The name of each shape or outcome a code can be decoded as.
+    labels: Vec<String>,
+    /// Each mapping of the response codes, by index, to the shapes or outcomes used by
+    /// [`Rules::respond`], with its total score. Sorted from best to worst, and otherwise in
+    /// lexicographic order of the mappings, which starts with the decoding of the puzzle.
+    results: Vec<(Vec<usize>, i64)>,
+}
+
+impl Search {
+    pub fn results(&self) -> &[(Vec<usize>, i64)] {
+        &self.results
+    }
+
+    /// The mapping with the highest score.
+    pub fn best(&self) -> (&[usize], i64) {
+        let (mapping, score) = self.results.first().unwrap();
+        (mapping, *score)
+    }
+
+    /// The mapping with the lowest score.
+    pub fn worst(&self) -> (&[usize], i64) {
+        let (mapping, score) = self.results.last().unwrap();
+        (mapping, *score)
+    }
+
+    /// The number of mappings reaching each score, from the highest score to the lowest.
+    pub fn distribution(&self) -> Vec<(i64, usize)> {
+        let mut distribution: Vec<(i64, usize)> = vec![];
+        for &(_, score) in &self.results {
+            match distribution.last_mut() {
+                Some((last, count)) if *last == score => *count += 1,
+                _ => distribution.push((score, 1)),
+            }
+        }
+        distribution
+    }
+
+    /// Describes `mapping` as e.g. `X=Rock Y=Paper Z=Scissors`.
+    pub fn describe(&self, mapping: &[usize]) -> String {
+        let codes = self.codes.iter().zip(mapping);
+        let pairs: Vec<String> = codes
+            .map(|(code, &i)| format!("{code}={}", self.labels[i]))
+            .collect();
+        pairs.join(" ")
+    }
+}
+
+impl Display for Search {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let decoded = match self.decoding {
+            Decoding::Shape => "shapes",
+            Decoding::Outcome => "outcomes",
+        };
+        writeln!(
+            f,
+            "{} decodings of the responses as {decoded}",
+            self.results.len()
+        )?;
+        let (best, worst) = (self.best(), self.worst());
+        let width = best.1.to_string().len().max(worst.1.to_string().len());
+        for (name, (mapping, score)) in [("Best ", best), ("Worst", worst)] {
+            writeln!(f, "{name}  {score:>width$}  {}", self.describe(mapping))?;
+        }
+        let distribution = self.distribution();
+        let width = (distribution.iter())
+            .map(|(score, _)| score.to_string().len())
+            .fold("Score".len(), usize::max);
+        write!(f, "{:>width$}  Decodings", "Score")?;
+        for (score, count) in distribution {
+            write!(f, "\n{score:>width$}  {count:>9}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Names the outcome decoded from the response code at `index`, see [`Rules::respond`].
+fn outcome_label(n: usize, index: usize) -> String {
+    let steps = index.abs_diff(n / 2);
+    let (outcome, sign) = match index.cmp(&(n / 2)) {
+        Ordering::Less => ("lose", '-'),
+        Ordering::Equal => return "draw".to_owned(),
+        Ordering::Greater => ("win", '+'),
+    };
+    // With three shapes, there is only one way to win or lose.
+    if n == 3 {
+        outcome.to_owned()
+    } else {
+        format!("{outcome}{sign}{steps}")
+    }
+}
+
+/// Rearranges `values` into the next permutation in lexicographic order, returning `false`
+/// once they are in descending order.
+fn next_permutation(values: &mut [usize]) -> bool {
+    let Some(i) = (1..values.len()).rev().find(|&i| values[i - 1] < values[i]) else {
+        return false;
+    };
+    let j = (i..values.len())
+        .rev()
+        .find(|&j| values[j] > values[i - 1])
+        .unwrap();
+    values.swap(i - 1, j);
+    values[i..].reverse();
+    true
+}
+
+fn code_index(codes: &[char], code: char, column: &str) -> Result<usize> {
+    codes
+        .iter()
+        .position(|&c| c == code)
+        .with_context(|| format!("invalid {column} code {code:?}"))
+}
+
+impl FromStr for Rules {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let lines = parse_lines_with(s, |line| {
+            if line.trim_start().starts_with('#') {
+                return Ok(None);
+            }
+            let (key, value) = line.split_once(':').context("expected colon after key")?;
+            Ok(Some((key.trim(), value.trim())))
+        });
+        let mut fields = vec![];
+        for field in lines {
+            if let Some((key, value)) = field? {
+                if fields.iter().any(|&(k, _)| k == key) {
+                    return Err(Error::msg(format!("duplicate rule {key:?}")));
+                }
+                fields.push((key, value));
+            }
+        }
+        if let Some((key, _)) = fields.iter().find(|(key, _)| !RULE_KEYS.contains(key)) {
+            return Err(Error::msg(format!("unknown rule {key:?}")));
+        }
+        let field = |key: &str| {
+            let (_, value) = fields
+                .iter()
+                .find(|&&(k, _)| k == key)
+                .with_context(|| format!("missing rule {key:?}"))?;
+            Ok::<_, Error>(*value)
+        };
+        let points = |key: &str| -> Result<Vec<i64>> {
+            field(key)?.split_whitespace().map(parse_at).collect()
+        };
+        let codes = |key: &str| -> Result<Vec<char>> {
+            Ok(field(key)?.chars().filter(|c| !c.is_whitespace()).collect())
+        };
+        let outcome_points = points("outcome points")?;
+        let outcome_points = outcome_points.as_slice().try_into().map_err(|_| {
+            Error::msg(format!(
+                "expected 3 outcome points for a loss, a draw and a win, found {}",
+                outcome_points.len()
+            ))
+        })?;
+        Rules::new(
+            field("shapes")?
+                .split_whitespace()
+                .map(str::to_owned)
+                .collect(),
+            points("shape points")?,
+            outcome_points,
+            codes("opponent")?,
+            codes("response")?,
+        )
+    }
+}
+
+const RULE_KEYS: [&str; 5] = [
+    "shapes",
+    "shape points",
+    "outcome points",
+    "opponent",
+    "response",
+];
+
+fn parse(input: &str) -> Result<Vec<(char, char)>> {
+    parse_lines_with(input, |line| {
+        let mut chars = line.chars();
+        Ok((
+            chars.next().context("line too short")?,
+            chars.nth(1).context("line too short")?,
+        ))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RPSLS: &str = "\
+# Rock paper scissors lizard Spock
+shapes: Rock Spock Paper Lizard Scissors
+shape points: 1 2 3 4 5
+outcome points: 0 3 6
+
+opponent: A B C D E
+response: V W X Y Z
+";
+
+    #[test]
+    fn outcomes() {
+        let rules: Rules = RPSLS.parse().unwrap();
+        let shape = |name: &str| (0..rules.len()).find(|&i| rules.shape_name(i) == name);
+        let beats = |a: &str, b: &str| {
+            let (a, b) = (shape(a).unwrap(), shape(b).unwrap());
+            rules.outcome(b, a) == Outcome::Win && rules.outcome(a, b) == Outcome::Loss
+        };
+        assert!(beats("Scissors", "Paper"));
+        assert!(beats("Paper", "Rock"));
+        assert!(beats("Rock", "Lizard"));
+        assert!(beats("Lizard", "Spock"));
+        assert!(beats("Spock", "Scissors"));
+        assert!(beats("Scissors", "Lizard"));
+        assert!(beats("Lizard", "Paper"));
+        assert!(beats("Paper", "Spock"));
+        assert!(beats("Spock", "Rock"));
+        assert!(beats("Rock", "Scissors"));
+
+        // Decoded as outcomes, V, W, X, Y and Z play lizard, scissors, rock, Spock and paper
+        // against rock.
+        let rounds = [('A', 'V'), ('A', 'W'), ('A', 'X'), ('A', 'Y'), ('A', 'Z')];
+        let score = |decoding| rules.total_score(&rounds, decoding).unwrap();
+        assert_eq!(score(Decoding::Shape), 4 + 8 + 9 + 4 + 5);
+        assert_eq!(score(Decoding::Outcome), 4 + 5 + 4 + 8 + 9);
+    }
+
+    #[test]
+    fn standard_rules() {
+        let rules = Rules::default();
+        let rounds = parse("A Y\nB X\nC Z\n").unwrap();
+        assert_eq!(rules.total_score(&rounds, Decoding::Shape).unwrap(), 15);
+        assert_eq!(rules.total_score(&rounds, Decoding::Outcome).unwrap(), 12);
+        let error = rules.total_score(&[('A', 'X'), ('D', 'X')], Decoding::Shape);
+        assert_eq!(
+            format!("{:#}", error.unwrap_err()),
+            "round 2: invalid opponent code 'D'"
+        );
+    }
+
+    #[test]
+    fn search() {
+        let rules = Rules::default();
+        let rounds = parse("A Y\nB X\nC Z\n").unwrap();
+        let search = rules.search(&rounds, Decoding::Shape).unwrap();
+        assert_eq!(search.best(), (&[2, 1, 0][..], 24));
+        assert_eq!(search.worst(), (&[0, 2, 1][..], 6));
+        assert_eq!(search.distribution(), [(24, 1), (15, 4), (6, 1)]);
+        assert_eq!(
+            search.to_string(),
+            "6 decodings of the responses as shapes\n\
+             Best   24  X=Scissors Y=Paper Z=Rock\n\
+             Worst   6  X=Rock Y=Scissors Z=Paper\n\
+             Score  Decodings\n   \
+                24          1\n   \
+                15          4\n    \
+                 6          1"
+        );
+        let search = rules.search(&rounds, Decoding::Outcome).unwrap();
+        assert_eq!(search.describe(&[0, 1, 2]), "X=lose Y=draw Z=win");
+        assert!(search.results().contains(&(vec![0, 1, 2], 12)));
+
+        let rpsls: Rules = RPSLS.parse().unwrap();
+        let rounds = parse("A V\nC Z\nE X\nB Y\nA Y\nD W\n").unwrap();
+        for decoding in [Decoding::Shape, Decoding::Outcome] {
+            let search = rpsls.search(&rounds, decoding).unwrap();
+            assert_eq!(search.results().len(), 120);
+            assert_eq!(
+                search.distribution().iter().map(|(_, n)| n).sum::<usize>(),
+                120
+            );
+            // Mapping the codes is the same as reordering the response alphabet.
+            for (mapping, score) in search.results() {
+                let mut rules = rpsls.clone();
+                for (r, &i) in mapping.iter().enumerate() {
+                    rules.response_codes[i] = rpsls.response_codes[r];
+                }
+                assert_eq!(rules.total_score(&rounds, decoding).unwrap(), *score);
+            }
+        }
+        let search = rpsls.search(&rounds, Decoding::Outcome).unwrap();
+        assert_eq!(
+            search.describe(&[0, 1, 2, 3, 4]),
+            "V=lose-2 W=lose-1 X=draw Y=win+1 Z=win+2"
+        );
+        let error = rules.search(&[('A', 'Q')], Decoding::Shape).unwrap_err();
+        assert_eq!(format!("{error:#}"), "round 1: invalid response code 'Q'");
+
+        let codes: Vec<char> = ('a'..='k').collect();
+        let shapes = codes.iter().map(char::to_string).collect();
+        let large = Rules::new(shapes, vec![1; 11], [0, 3, 6], codes.clone(), codes).unwrap();
+        let error = large.search(&[], Decoding::Shape).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "cannot search the decodings of 11 response codes, at most 9 are supported"
+        );
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let error = |rules: &str| format!("{:#}", rules.parse::<Rules>().unwrap_err());
+        let standard = |from: &str, to: &str| error(&STANDARD_RULES.replace(from, to));
+        assert_eq!(
+            standard(" Scissors", ""),
+            "the number of shapes must be odd, found 2"
+        );
+        assert_eq!(standard("1 2 3", "1 2"), "expected 3 shape points, found 2");
+        assert_eq!(
+            standard("XYZ", "XYZW"),
+            "expected 3 response codes, found 4"
+        );
+        assert_eq!(standard("ABC", "ABA"), "duplicate opponent code 'A'");
+        assert_eq!(
+            standard("0 3 6", "0 6"),
+            "expected 3 outcome points for a loss, a draw and a win, found 2"
+        );
+        assert_eq!(standard("opponent: ABC\n", ""), "missing rule \"opponent\"");
+        assert_eq!(standard("shapes", "shape"), "unknown rule \"shape\"");
+        assert!(standard("0 3 6", "0 x 6").starts_with("invalid digit"));
+        assert_eq!(
+            error(&format!("{STANDARD_RULES}response: XYZ\n")),
+            "duplicate rule \"response\""
+        );
+    }
+}
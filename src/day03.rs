@@ -0,0 +1,93 @@
+//! Day 3: Rucksack Reorganization
+
+use crate::{with_span, Solution};
+use anyhow::{Context, Error, Result};
+use std::ops::BitAnd;
+
+/// Finds the misplaced items and group badges in the rucksacks.
+///
+/// The parsed input is the sequence of item priorities, with rucksacks separated by `u8::MAX`.
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Vec<u8>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let input = input.trim();
+        input
+            .char_indices()
+            .map(|(i, c)| priority(c).map_err(|e| with_span(&input[i..i + c.len_utf8()], e)))
+            .collect()
+    }
+
+    fn part1(priorities: &Self::Parsed) -> Result<u32> {
+        (1..)
+            .zip(rucksacks(priorities))
+            .map(|(i, rucksack)| common_item(rucksack).with_context(|| format!("rucksack {i}")))
+            .sum()
+    }
+
+    fn part2(priorities: &Self::Parsed) -> Result<u32> {
+        (1..)
+            .zip(rucksacks(priorities).chunks(3))
+            .map(|(i, group)| badge(group).with_context(|| format!("group {i}")))
+            .sum()
+    }
+}
+
+const NEWLINE: u8 = u8::MAX;
+
+fn rucksacks(priorities: &[u8]) -> Vec<&[u8]> {
+    priorities.split(|&i| i == NEWLINE).collect()
+}
+
+fn priority(item: char) -> Result<u8> {
+    match item {
+        'a'..='z' => Ok(item as u8 - b'a' + 1),
+        'A'..='Z' => Ok(item as u8 - b'A' + 27),
+        '\n' => Ok(NEWLINE),
+        _ => Err(Error::msg("invalid item")),
+    }
+}
+
+fn bitset(rucksack: &[u8]) -> u64 {
+    rucksack.iter().fold(0, |acc, &i| acc | (1 << i))
+}
+
+/// The priority of the only item in `items`.
+fn single_item(items: u64) -> Result<u32> {
+    match items.count_ones() {
+        1 => Ok(items.trailing_zeros()),
+        n => Err(Error::msg(format!(
+            "expected exactly one common item, found {n}"
+        ))),
+    }
+}
+
+fn common_item(rucksack: &[u8]) -> Result<u32> {
+    if !rucksack.len().is_multiple_of(2) {
+        return Err(Error::msg(format!(
+            "{} items cannot be split into two compartments",
+            rucksack.len()
+        )));
+    }
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
+    single_item(bitset(first) & bitset(second))
+}
+
+fn badge(rucksacks: &[&[u8]]) -> Result<u32> {
+    if rucksacks.len() != 3 {
+        return Err(Error::msg(format!(
+            "expected 3 rucksacks, found {}",
+            rucksacks.len()
+        )));
+    }
+    single_item(
+        rucksacks
+            .iter()
+            .map(|r| bitset(r))
+            .fold(u64::MAX, BitAnd::bitand),
+    )
+}
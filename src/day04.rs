@@ -0,0 +1,70 @@
+//! Day 4: Camp Cleanup
+
+use crate::{parse_at, parse_lines_with, Solution};
+use anyhow::{Context, Error, Result};
+use std::str::FromStr;
+
+/// Counts pairs of section assignments that contain or overlap each other.
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Vec<[Range; 2]>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_lines_with(input, parse_pair).collect()
+    }
+
+    fn part1(pairs: &Self::Parsed) -> Result<usize> {
+        Ok(count(pairs, Range::containing))
+    }
+
+    fn part2(pairs: &Self::Parsed) -> Result<usize> {
+        Ok(count(pairs, Range::overlapping))
+    }
+}
+
+fn count<F>(pairs: &[[Range; 2]], predicate: F) -> usize
+where
+    F: Fn(&Range, &Range) -> bool,
+{
+    pairs.iter().filter(|&p| predicate(&p[0], &p[1])).count()
+}
+
+/// An inclusive range of section IDs, parsed from `start-end`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Range {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Range {
+    /// Whether either range fully contains the other one.
+    pub fn containing(&self, other: &Self) -> bool {
+        (self.start <= other.start && other.end <= self.end)
+            || (other.start <= self.start && self.end <= other.end)
+    }
+
+    /// Whether the ranges have at least one section in common.
+    pub fn overlapping(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+impl FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').context("range spec contains no hyphen")?;
+        Ok(Self {
+            start: parse_at(start)?,
+            end: parse_at(end)?,
+        })
+    }
+}
+
+fn parse_pair(line: &str) -> Result<[Range; 2]> {
+    let (first, second) = line.split_once(',').context("line contains no comma")?;
+    Ok([parse_at(first)?, parse_at(second)?])
+}
@@ -0,0 +1,192 @@
+//! Day 5: Supply Stacks
+
+use crate::{from_regex, get_pair_mut, parse_at, parse_lines, split_blocks, Solution};
+use anyhow::{Context, Error, Result};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+/// Rearranges the crate stacks with both crane models and reports the top crates.
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = (Stacks, Vec<Step>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1((stacks, steps): &Self::Parsed) -> Result<String> {
+        stacks.clone().apply_all(steps, CrateMover9000)
+    }
+
+    fn part2((stacks, steps): &Self::Parsed) -> Result<String> {
+        stacks.clone().apply_all(steps, CrateMover9001)
+    }
+}
+
+/// The crate stacks, keyed by their single-character labels.
+#[derive(Clone, Debug)]
+pub struct Stacks {
+    labels: Vec<char>,
+    crates: HashMap<char, Vec<char>>,
+}
+
+/// The crane model determines whether crates are moved one at a time or all at once.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CraneModel {
+    CrateMover9000,
+    CrateMover9001,
+}
+
+pub use CraneModel::*;
+
+impl Stacks {
+    /// Moves crates according to a single rearrangement step.
+    pub fn apply(&mut self, step: &Step, model: CraneModel) -> Result<()> {
+        if step.from == step.to {
+            return Err(Error::msg("source and destination stack must differ"));
+        }
+        let (from, to) = get_pair_mut(&mut self.crates, &step.from, &step.to)
+            .context("invalid source or destination stack")?;
+        let i = from
+            .len()
+            .checked_sub(step.count)
+            .context("not enough crates on source stack")?;
+        match model {
+            CrateMover9000 => to.extend(from.drain(i..).rev()),
+            CrateMover9001 => to.extend(from.drain(i..)),
+        }
+        Ok(())
+    }
+
+    /// Applies all steps and returns the labels of the crates on top of each stack.
+    pub fn apply_all(&mut self, steps: &[Step], model: CraneModel) -> Result<String> {
+        for step in steps {
+            self.apply(step, model)?;
+        }
+        Ok(self.top_crates())
+    }
+
+    /// The labels of the crates on top of each stack, skipping empty stacks.
+    pub fn top_crates(&self) -> String {
+        self.labels
+            .iter()
+            .flat_map(|label| self.crates[label].last().cloned())
+            .collect()
+    }
+}
+
+/// Draws the stacks in the format of the puzzle input.
+impl Display for Stacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let height = self.crates.values().map(Vec::len).max().unwrap_or(0);
+        for y in (0..height).rev() {
+            let row: Vec<String> = self
+                .labels
+                .iter()
+                .map(|label| match self.crates[label].get(y) {
+                    Some(c) => format!("[{c}]"),
+                    None => "   ".to_owned(),
+                })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+        let labels: Vec<String> = self.labels.iter().map(|l| format!(" {l} ")).collect();
+        write!(f, "{}", labels.join(" "))
+    }
+}
+
+/// The rearrangement of the stacks, one step at a time.
+#[cfg(feature = "visualize")]
+pub struct Rearrangement {
+    stacks: Stacks,
+    steps: Vec<Step>,
+    model: CraneModel,
+    done: usize,
+}
+
+#[cfg(feature = "visualize")]
+impl Rearrangement {
+    pub fn new(stacks: Stacks, steps: Vec<Step>, model: CraneModel) -> Self {
+        Self {
+            stacks,
+            steps,
+            model,
+            done: 0,
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl crate::animation::Animation for Rearrangement {
+    fn step(&mut self) -> Result<bool> {
+        let Some(step) = self.steps.get(self.done) else {
+            return Ok(false);
+        };
+        self.stacks.apply(step, self.model)?;
+        self.done += 1;
+        Ok(true)
+    }
+
+    fn frame(&self) -> String {
+        self.stacks.to_string()
+    }
+
+    fn caption(&self) -> String {
+        format!(
+            "{} of {} steps, top crates {}",
+            self.done,
+            self.steps.len(),
+            self.stacks.top_crates()
+        )
+    }
+}
+
+impl FromStr for Stacks {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().rev();
+        let stacks: Vec<(usize, char)> = lines
+            .next()
+            .context("empty stack spec")?
+            .char_indices()
+            .filter(|&(_, label)| label != ' ')
+            .collect();
+        let mut crates: HashMap<_, _> = stacks.iter().map(|&(_, label)| (label, vec![])).collect();
+        for line in lines {
+            for &(i, label) in &stacks {
+                if let Some(c) = line.get(i..).and_then(|rest| rest.chars().next()) {
+                    if c != ' ' {
+                        crates.get_mut(&label).unwrap().push(c);
+                    }
+                }
+            }
+        }
+        let labels = stacks.iter().map(|&(_, label)| label).collect();
+        Ok(Self { labels, crates })
+    }
+}
+
+/// A rearrangement step, parsed from `move <count> from <from> to <to>`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub count: usize,
+    pub from: char,
+    pub to: char,
+}
+
+from_regex!(Step, r"move (?P<count>\S+) from (?P<from>\S) to (?P<to>\S)", {
+    count,
+    from,
+    to,
+});
+
+fn parse_input(input: &str) -> Result<(Stacks, Vec<Step>)> {
+    let [stacks, steps] = split_blocks(input).context("expected stacks and steps")?;
+    Ok((
+        parse_at(stacks.text)?,
+        parse_lines(steps.text).collect::<Result<_>>()?,
+    ))
+}
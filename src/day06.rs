@@ -0,0 +1,61 @@
+//! Day 6: Tuning Trouble
+
+use crate::{with_span, Solution};
+use anyhow::{Error, Result};
+
+/// Finds the start-of-packet and start-of-message markers in the datastream.
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Vec<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1(packet: &Self::Parsed) -> Result<usize> {
+        start_pos(packet, 4)
+    }
+
+    fn part2(packet: &Self::Parsed) -> Result<usize> {
+        start_pos(packet, 14)
+    }
+}
+
+/// Returns the number of characters processed until the last `len` characters are all distinct.
+/// The packet may only contain lowercase letters.
+pub fn start_pos(packet: &[u8], len: usize) -> Result<usize> {
+    if let Some(&c) = packet.iter().find(|c| !c.is_ascii_lowercase()) {
+        return Err(Error::msg(format!(
+            "invalid character {:?} in packet",
+            c as char
+        )));
+    }
+    let mut i = 0;
+    'outer: while i + len <= packet.len() {
+        let mut set = 0u32;
+        for j in (i..i + len).rev() {
+            let mask = 1 << (packet[j] - b'a');
+            if set & mask != 0 {
+                i = j + 1;
+                continue 'outer;
+            }
+            set |= mask;
+        }
+        return Ok(i + len);
+    }
+    Err(Error::msg("no start marker found"))
+}
+
+fn parse_input(input: &str) -> Result<Vec<u8>> {
+    let packet = input.trim();
+    match packet.find(|c: char| !c.is_ascii_lowercase()) {
+        None => Ok(packet.as_bytes().to_vec()),
+        Some(i) => Err(with_span(
+            &packet[i..],
+            Error::msg("invalid character in input"),
+        )),
+    }
+}
@@ -0,0 +1,144 @@
+//! Day 7: No Space Left On Device
+
+use crate::{parse_at, with_span, Solution};
+use anyhow::{Context, Error, Result};
+use std::collections::HashMap;
+
+/// Reconstructs the file system from the terminal session and finds directories to delete.
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = FileSystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let mut fs = FileSystem::reconstruct(input)?;
+        fs.calculate_sizes()?;
+        Ok(fs)
+    }
+
+    fn part1(fs: &Self::Parsed) -> Result<usize> {
+        Ok(fs.total_size(100_000))
+    }
+
+    fn part2(fs: &Self::Parsed) -> Result<usize> {
+        fs.find_directory_to_delete(40_000_000)
+    }
+}
+
+struct INode {
+    mode: Mode,
+    parent: usize,
+    size: usize,
+}
+
+impl INode {
+    fn directory(parent: usize) -> Self {
+        Self {
+            mode: Mode::Directory,
+            parent,
+            size: 0,
+        }
+    }
+
+    fn file(parent: usize, size: usize) -> Self {
+        Self {
+            mode: Mode::File,
+            parent,
+            size,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq)]
+enum Mode {
+    File,
+    Directory,
+}
+
+/// A file system tree reconstructed from `cd` and `ls` commands.
+///
+/// Inodes are stored in the order they were discovered, so every inode comes after its parent.
+/// The root directory is at index 0.
+pub struct FileSystem {
+    inodes: Vec<INode>,
+}
+
+impl FileSystem {
+    /// Replays a terminal session to reconstruct the file system.
+    pub fn reconstruct<'a>(session: &'a str) -> Result<Self> {
+        let mut inodes = vec![INode::directory(0)];
+        let mut dirs: HashMap<(usize, &'a str), usize> = HashMap::new();
+        let mut cwd = 0;
+        let mut replay_line = |line: &'a str| -> Result<()> {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("$") => match tokens.next() {
+                    Some("cd") => match tokens.next() {
+                        Some("/") => cwd = 0,
+                        Some("..") => cwd = inodes[cwd].parent,
+                        Some(dir) => cwd = *dirs.get(&(cwd, dir)).context("directory not found")?,
+                        None => return Err(Error::msg("expected directory name after cd")),
+                    },
+                    Some("ls") | None => {}
+                    Some(_) => return Err(Error::msg("unexpected command")),
+                },
+                Some("dir") => {
+                    let name = tokens.next().context("expected directory name")?;
+                    dirs.insert((cwd, name), inodes.len());
+                    inodes.push(INode::directory(cwd));
+                }
+                Some(size) => {
+                    tokens.next().context("expected file name")?;
+                    inodes.push(INode::file(cwd, parse_at(size)?));
+                }
+                None => {}
+            }
+            match tokens.next() {
+                Some(token) => Err(with_span(token, Error::msg("unexpected token"))),
+                None => Ok(()),
+            }
+        };
+        for line in session.lines() {
+            replay_line(line).map_err(|e| with_span(line, e))?;
+        }
+        Ok(Self { inodes })
+    }
+
+    /// Accumulates the sizes of all files into their ancestor directories.
+    pub fn calculate_sizes(&mut self) -> Result<()> {
+        for i in (1..self.inodes.len()).rev() {
+            let parent = self.inodes[i].parent;
+            let size = self.inodes[i].size;
+            self.inodes[parent].size = self.inodes[parent]
+                .size
+                .checked_add(size)
+                .context("total size too large")?;
+        }
+        Ok(())
+    }
+
+    /// Sums the sizes of all directories with a size of at most `limit`.
+    pub fn total_size(&self, limit: usize) -> usize {
+        self.inodes
+            .iter()
+            .filter(|inode| inode.mode == Mode::Directory && inode.size <= limit)
+            .map(|inode| inode.size)
+            .sum()
+    }
+
+    /// Returns the size of the smallest directory that brings the used space down to `max_used`.
+    pub fn find_directory_to_delete(&self, max_used: usize) -> Result<usize> {
+        let space_to_free = self.inodes[0]
+            .size
+            .checked_sub(max_used)
+            .context("already enough free space")?;
+        self.inodes
+            .iter()
+            .filter(|inode| inode.mode == Mode::Directory && inode.size >= space_to_free)
+            .map(|inode| inode.size)
+            .min()
+            .context("no directory is large enough")
+    }
+}
@@ -0,0 +1,121 @@
+//! Day 8: Treetop Tree House
+
+use crate::{with_span, Grid, Point, Solution, NEIGHBOURS4};
+use anyhow::{Error, Result};
+
+/// Counts the trees visible from outside the grid and finds the best scenic score.
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Trees;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let mut trees = Trees::from_input(input)?;
+        trees.mark_visible();
+        Ok(trees)
+    }
+
+    fn part1(trees: &Self::Parsed) -> Result<usize> {
+        Ok(trees.count_visible())
+    }
+
+    fn part2(trees: &Self::Parsed) -> Result<usize> {
+        Ok(trees.max_scenic_score())
+    }
+}
+
+/// The tree heights, along with which trees are visible from outside the grid.
+pub struct Trees {
+    heights: Grid<u8>,
+    visible: Grid<bool>,
+}
+
+impl Trees {
+    pub fn from_input(input: &str) -> Result<Self> {
+        let heights = Grid::from_lines(input.lines(), |line| {
+            line.char_indices().map(|(i, c)| match c {
+                '0'..='9' => Ok(c as u8),
+                _ => Err(with_span(
+                    &line[i..i + c.len_utf8()],
+                    Error::msg("invalid tree height"),
+                )),
+            })
+        })?;
+        let visible = Grid::new(heights.width(), heights.height());
+        Ok(Self { heights, visible })
+    }
+
+    pub fn heights(&self) -> &Grid<u8> {
+        &self.heights
+    }
+
+    /// Which trees are visible from outside the grid, once they have been marked.
+    pub fn visible(&self) -> &Grid<bool> {
+        &self.visible
+    }
+
+    fn mark_visible_line(&mut self, line: impl Iterator<Item = usize>) {
+        let mut height = 0;
+        for pos in line {
+            if self.heights[pos] > height {
+                self.visible[pos] = true;
+                height = self.heights[pos];
+            }
+        }
+    }
+
+    /// Marks all trees visible from any edge of the grid.
+    pub fn mark_visible(&mut self) {
+        for x in 0..self.heights.width() {
+            self.mark_visible_line(self.heights.column_indices(x));
+            self.mark_visible_line(self.heights.column_indices(x).rev());
+        }
+        for y in 0..self.heights.height() {
+            self.mark_visible_line(self.heights.row_indices(y));
+            self.mark_visible_line(self.heights.row_indices(y).rev());
+        }
+    }
+
+    pub fn count_visible(&self) -> usize {
+        self.visible.iter().filter(|&&v| v).count()
+    }
+
+    /// The number of trees seen from `pos` when looking in the direction `(dx, dy)`.
+    fn viewing_distance(&self, pos: Point, (dx, dy): (isize, isize)) -> usize {
+        let height_at = |p: Point| self.heights[self.heights.index_unchecked(p)];
+        let height = height_at(pos);
+        let mut count = 0;
+        for p in self.heights.ray(pos, dx, dy) {
+            count += 1;
+            if height <= height_at(p) {
+                break;
+            }
+        }
+        count
+    }
+
+    /// The product of the viewing distances in all four directions from the tree at `pos`.
+    pub fn scenic_score(&self, pos: Point) -> usize {
+        NEIGHBOURS4
+            .into_iter()
+            .map(|direction| self.viewing_distance(pos, direction))
+            .product()
+    }
+
+    /// The scenic scores of all trees.
+    pub fn scenic_scores(&self) -> Grid<usize> {
+        Grid::from_fn(self.heights.width(), self.heights.height(), |pos| {
+            self.scenic_score(pos)
+        })
+    }
+
+    pub fn max_scenic_score(&self) -> usize {
+        self.heights
+            .points()
+            .map(|pos| self.scenic_score(pos))
+            .max()
+            .unwrap_or_default()
+    }
+}
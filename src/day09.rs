@@ -0,0 +1,232 @@
+//! Day 9: Rope Bridge
+
+use crate::{parse_at, parse_lines_with, Solution, SparseGrid};
+use anyhow::{Context, Error, Result};
+use std::{cmp::Ordering, str::FromStr};
+
+/// Counts the positions visited by the tail of a rope with 2 and 10 knots.
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<(Direction, u32)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1(motions: &Self::Parsed) -> Result<usize> {
+        tail_positions(motions, 2)
+    }
+
+    fn part2(motions: &Self::Parsed) -> Result<usize> {
+        tail_positions(motions, 10)
+    }
+}
+
+/// Returns the number of distinct positions the tail of a rope with `len` knots visits.
+pub fn tail_positions(motions: &[(Direction, u32)], len: usize) -> Result<usize> {
+    let mut rope = Rope::new(len)?;
+    rope.apply_motions(motions);
+    Ok(rope.track.len())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Direction {
+    Right,
+    Up,
+    Left,
+    Down,
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dir = match s {
+            "R" => Direction::Right,
+            "U" => Direction::Up,
+            "L" => Direction::Left,
+            "D" => Direction::Down,
+            _ => return Err(Error::msg("invalid direction")),
+        };
+        Ok(dir)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Position {
+    fn mov(&mut self, dir: Direction) {
+        match dir {
+            Direction::Right => self.x += 1,
+            Direction::Up => self.y += 1,
+            Direction::Left => self.x -= 1,
+            Direction::Down => self.y -= 1,
+        }
+    }
+
+    fn max_dist(self, other: Position) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    fn trail(&mut self, other: Position) -> bool {
+        if self.max_dist(other) < 2 {
+            false
+        } else {
+            match self.x.cmp(&other.x) {
+                Ordering::Less => self.x += 1,
+                Ordering::Equal => {}
+                Ordering::Greater => self.x -= 1,
+            }
+            match self.y.cmp(&other.y) {
+                Ordering::Less => self.y += 1,
+                Ordering::Equal => {}
+                Ordering::Greater => self.y -= 1,
+            }
+            true
+        }
+    }
+}
+
+/// A rope with a number of knots, tracking the positions visited by its tail.
+pub struct Rope {
+    pub knots: Vec<Position>,
+    pub track: SparseGrid<()>,
+}
+
+impl Rope {
+    pub fn new(len: usize) -> Result<Self> {
+        if len == 0 {
+            return Err(Error::msg("a rope needs at least one knot"));
+        }
+        let knots = vec![Position::default(); len];
+        let mut rope = Self {
+            knots,
+            track: SparseGrid::new(),
+        };
+        rope.mark_tail();
+        Ok(rope)
+    }
+
+    /// Moves the head by one step and lets the other knots follow.
+    pub fn mov(&mut self, dir: Direction) {
+        self.knots[0].mov(dir);
+        for i in 1..self.knots.len() {
+            let front_knot = self.knots[i - 1];
+            if !self.knots[i].trail(front_knot) {
+                return;
+            }
+        }
+        self.mark_tail();
+    }
+
+    fn mark_tail(&mut self) {
+        let tail = self.knots.last().unwrap();
+        self.track.insert(tail.x, tail.y, ());
+    }
+
+    pub fn apply_motions(&mut self, motions: &[(Direction, u32)]) {
+        for &(dir, count) in motions {
+            for _ in 0..count {
+                self.mov(dir);
+            }
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Direction, u32)>> {
+    parse_lines_with(input, |line| {
+        let (dir, count) = line
+            .split_once(' ')
+            .context("input line contains no space character")?;
+        Ok((parse_at(dir)?, parse_at(count)?))
+    })
+    .collect()
+}
+
+/// The motions of a rope, one step of the head at a time.
+#[cfg(feature = "visualize")]
+pub struct RopeMotion {
+    rope: Rope,
+    motions: Vec<(Direction, u32)>,
+    motion: usize,
+    moved: u32,
+    steps: usize,
+}
+
+#[cfg(feature = "visualize")]
+impl RopeMotion {
+    /// The size of the part of the bridge drawn around the head.
+    const VIEW: (i64, i64) = (61, 21);
+
+    pub fn new(motions: Vec<(Direction, u32)>, len: usize) -> Result<Self> {
+        Ok(Self {
+            rope: Rope::new(len)?,
+            motions,
+            motion: 0,
+            moved: 0,
+            steps: 0,
+        })
+    }
+
+    /// Labels the knots as in the puzzle, with `T` for the tail of a rope with two knots and
+    /// numbers otherwise.
+    fn knot_label(&self, i: usize) -> char {
+        match i {
+            0 => 'H',
+            _ if self.rope.knots.len() == 2 => 'T',
+            _ => char::from_digit(i as u32 % 10, 10).unwrap(),
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl crate::animation::Animation for RopeMotion {
+    fn step(&mut self) -> Result<bool> {
+        while let Some(&(dir, count)) = self.motions.get(self.motion) {
+            if self.moved < count {
+                self.rope.mov(dir);
+                self.moved += 1;
+                self.steps += 1;
+                return Ok(true);
+            }
+            self.motion += 1;
+            self.moved = 0;
+        }
+        Ok(false)
+    }
+
+    /// Draws the bridge around the head, with `y` increasing upwards as in the puzzle.
+    fn frame(&self) -> String {
+        let head = self.rope.knots[0];
+        let (width, height) = Self::VIEW;
+        let mut s = String::new();
+        for y in (head.y - height / 2..=head.y + height / 2).rev() {
+            for x in head.x - width / 2..=head.x + width / 2 {
+                let knot = self.rope.knots.iter().position(|k| (k.x, k.y) == (x, y));
+                s.push(match knot {
+                    Some(i) => self.knot_label(i),
+                    None if (x, y) == (0, 0) => 's',
+                    None if self.rope.track.contains(x, y) => '#',
+                    None => '.',
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    fn caption(&self) -> String {
+        format!(
+            "{} steps, tail visited {} positions",
+            self.steps,
+            self.rope.track.len()
+        )
+    }
+}
@@ -0,0 +1,151 @@
+//! Day 10: Cathode-Ray Tube
+
+use crate::{ocr, parse_at, parse_lines, Solution};
+use anyhow::{Context, Error, Result};
+use std::{fmt::Display, str::FromStr};
+
+/// Runs the CPU program to compute the signal strength and draw the CRT image.
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = Screen;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_lines(input).collect()
+    }
+
+    fn part1(instructions: &Self::Parsed) -> Result<i64> {
+        Ok(total_signal(instructions))
+    }
+
+    fn part2(instructions: &Self::Parsed) -> Result<Screen> {
+        Ok(Screen(render(instructions)))
+    }
+}
+
+/// The width of the CRT in pixels.
+pub const SCREEN_WIDTH: usize = 40;
+
+/// The image drawn on the CRT, one `char` per pixel in row-major order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Screen(pub Vec<char>);
+
+impl Screen {
+    pub fn rows(&self) -> impl Iterator<Item = String> + '_ {
+        self.0
+            .chunks_exact(SCREEN_WIDTH)
+            .map(|row| row.iter().collect())
+    }
+
+    /// The letters drawn on the screen.
+    pub fn text(&self) -> Result<String> {
+        ocr::decode_pixels(&self.0, SCREEN_WIDTH)
+    }
+}
+
+impl Display for Screen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows: Vec<String> = self.rows().collect();
+        rows.join("\n").fmt(f)
+    }
+}
+
+/// The sum of the signal strengths during the 20th, 60th, 100th, ... cycles.
+pub fn total_signal(instructions: &[Instruction]) -> i64 {
+    Cycles::new(instructions)
+        .skip(19)
+        .step_by(40)
+        .map(|(cycle, x)| i64::from(cycle) * x)
+        .sum()
+}
+
+/// Draws the CRT image, with `█` for lit and a space for dark pixels.
+pub fn render(instructions: &[Instruction]) -> Vec<char> {
+    Cycles::new(instructions)
+        .map(|(cycle, x)| {
+            let column = (cycle - 1) % SCREEN_WIDTH as u32;
+            if x.abs_diff(i64::from(column)) <= 1 {
+                '█'
+            } else {
+                ' '
+            }
+        })
+        .collect()
+}
+
+/// The cycles of the CPU with the value of the X register during each of them.
+///
+/// X is wider than the operands, so that even 240 additions of the largest operands cannot
+/// overflow it.
+struct Cycles<'a> {
+    instructions: std::slice::Iter<'a, Instruction>,
+    cycle: u32,
+    x: i64,
+    change: i64,
+    delay: u32,
+}
+
+impl<'a> Cycles<'a> {
+    fn new(instructions: &'a [Instruction]) -> Self {
+        Self {
+            instructions: instructions.iter(),
+            cycle: 0,
+            x: 1,
+            change: 0,
+            delay: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Cycles<'a> {
+    type Item = (u32, i64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cycle += 1;
+        if self.cycle > 240 {
+            return None;
+        }
+        if self.delay == 0 {
+            self.x += self.change;
+            loop {
+                match self.instructions.next() {
+                    Some(&Noop) => self.delay += 1,
+                    Some(&Addx(change)) => {
+                        self.delay += 2;
+                        self.change = change.into();
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        }
+        self.delay = self.delay.saturating_sub(1);
+        Some((self.cycle, self.x))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+pub use Instruction::*;
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("noop") => Ok(Noop),
+            Some("addx") => {
+                let num = parse_at(tokens.next().context("missing operand")?)?;
+                Ok(Addx(num))
+            }
+            _ => Err(Error::msg("invalid instruction")),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Day 11: Monkey in the Middle
+
+use crate::{from_regex, parse_at, parse_blocks, parse_split_comma, with_span, Solution};
+use anyhow::{Context, Error, Result};
+use std::{cmp::Reverse, str::FromStr};
+
+/// Simulates the monkeys throwing items and computes the level of monkey business.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Troop;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(troop: &Self::Parsed) -> Result<u64> {
+        let mut troop = troop.clone();
+        troop.play_rounds(20, 3)?;
+        troop.monkey_business()
+    }
+
+    fn part2(troop: &Self::Parsed) -> Result<u64> {
+        let mut troop = troop.clone();
+        troop.play_rounds(10_000, 1)?;
+        troop.monkey_business()
+    }
+}
+
+/// All monkeys, along with the product of their divisors used to keep worry levels small.
+#[derive(Clone, Debug)]
+pub struct Troop {
+    pub monkeys: Vec<Monkey>,
+    modulo: u64,
+}
+
+impl Troop {
+    /// Plays `count` rounds, dividing worry levels by `divide_by` after each inspection.
+    ///
+    /// Fails if `divide_by` is zero or a worry level overflows before it is reduced.
+    pub fn play_rounds(&mut self, count: usize, divide_by: u64) -> Result<()> {
+        if divide_by == 0 {
+            return Err(Error::msg("cannot divide worry levels by zero"));
+        }
+        for _ in 0..count {
+            for i in 0..self.monkeys.len() {
+                let thrown = self.monkeys[i]
+                    .play(divide_by, self.modulo)
+                    .with_context(|| format!("monkey {i} failed"))?;
+                for (j, item) in thrown {
+                    self.monkeys[j].items.push(item);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The product of the activities of the two most active monkeys.
+    pub fn monkey_business(&mut self) -> Result<u64> {
+        self.monkeys.sort_by_key(|monkey| Reverse(monkey.activity));
+        match self.monkeys.as_slice() {
+            [first, second, ..] => first
+                .activity
+                .checked_mul(second.activity)
+                .context("monkey business too large"),
+            _ => Err(Error::msg(format!(
+                "expected at least 2 monkeys, found {}",
+                self.monkeys.len()
+            ))),
+        }
+    }
+}
+
+impl FromStr for Troop {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let monkeys = parse_blocks(input).collect::<Result<Vec<Monkey>>>()?;
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for target in [monkey.if_true, monkey.if_false] {
+                if target >= monkeys.len() {
+                    return Err(Error::msg(format!(
+                        "monkey {i} throws to monkey {target}, but there are only {} monkeys",
+                        monkeys.len()
+                    )));
+                }
+            }
+        }
+        let modulo = monkeys
+            .iter()
+            .try_fold(1u64, |modulo, monkey| modulo.checked_mul(monkey.divisor))
+            .context("product of divisors too large")?;
+        Ok(Self { monkeys, modulo })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Monkey {
+    pub items: Vec<u64>,
+    pub operation: Operation,
+    pub divisor: u64,
+    /// The monkey to throw to if the test succeeds.
+    pub if_true: usize,
+    /// The monkey to throw to if the test fails.
+    pub if_false: usize,
+    /// The number of items inspected so far.
+    pub activity: u64,
+}
+
+impl Monkey {
+    fn play(&mut self, divide_by: u64, modulo: u64) -> Result<Vec<(usize, u64)>> {
+        self.items
+            .drain(..)
+            .map(|item| {
+                self.activity += 1;
+                let item = self.operation.apply(item)? / divide_by % modulo;
+                let next = if item.is_multiple_of(self.divisor) {
+                    self.if_true
+                } else {
+                    self.if_false
+                };
+                Ok((next, item))
+            })
+            .collect()
+    }
+}
+
+const MONKEY_REGEX: &str = r#"Monkey \d+:\r?
+  Starting items: (?P<items>.*)\r?
+  Operation: new = old (?P<operation>.*)\r?
+  Test: divisible by (?P<divisor>\S+)\r?
+    If true: throw to monkey (?P<if_true>\S+)\r?
+    If false: throw to monkey (?P<if_false>\S+)"#;
+
+from_regex!(Monkey, MONKEY_REGEX, {
+    items: |items| parse_split_comma(items).collect(),
+    operation,
+    divisor: |divisor| match parse_at(divisor)? {
+        0 => Err(Error::msg("must be positive")),
+        divisor => Ok(divisor),
+    },
+    if_true,
+    if_false,
+    activity = 0,
+});
+
+#[derive(Clone, Debug)]
+pub enum Operation {
+    Add(u64),
+    Mul(u64),
+    Square,
+}
+
+impl FromStr for Operation {
+    type Err = Error;
+
+    /// Parses the `<operator> <operand>` following `new = old`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (operator, operand) = s
+            .trim_end()
+            .split_once(' ')
+            .context("expected operator and operand")?;
+        match operand {
+            "old" => match operator {
+                "+" => Ok(Operation::Mul(2)),
+                "*" => Ok(Operation::Square),
+                _ => Err(with_span(operator, Error::msg("invalid operator"))),
+            },
+            _ => {
+                let operand: u64 = parse_at(operand)?;
+                match operator {
+                    "+" => Ok(Operation::Add(operand)),
+                    "*" => Ok(Operation::Mul(operand)),
+                    _ => Err(with_span(operator, Error::msg("invalid operator"))),
+                }
+            }
+        }
+    }
+}
+
+impl Operation {
+    fn apply(&self, old: u64) -> Result<u64> {
+        match *self {
+            Operation::Add(operand) => old.checked_add(operand),
+            Operation::Mul(operand) => old.checked_mul(operand),
+            Operation::Square => old.checked_mul(old),
+        }
+        .with_context(|| format!("worry level {old} overflows"))
+    }
+}
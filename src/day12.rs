@@ -0,0 +1,183 @@
+//! Day 12: Hill Climbing Algorithm
+
+use crate::{
+    search::{bfs, Search},
+    with_span, Grid, Solution,
+};
+use anyhow::{Context, Error, Result};
+use std::str::FromStr;
+
+/// Finds the fewest steps from the start, or from any square of height `a`, to the end.
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Parsed) -> Result<usize> {
+        map.find_path(Map::is_start)
+            .context("no path to target found")
+    }
+
+    fn part2(map: &Self::Parsed) -> Result<usize> {
+        map.find_path(Map::is_height_a)
+            .context("no path to target found")
+    }
+}
+
+/// The height map, with the start and end positions as grid indices.
+#[derive(Clone)]
+pub struct Map {
+    pub heights: Grid<u8>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Map {
+    /// Searches backwards from the end and returns the distance to the nearest square matching
+    /// `finished`.
+    pub fn find_path<F>(&self, finished: F) -> Option<usize>
+    where
+        F: Fn(&Self, usize) -> bool,
+    {
+        self.search(finished).goal_distance()
+    }
+
+    /// Searches backwards from the end, stepping to squares at most one lower, until a square
+    /// matching `finished` is reached.
+    pub fn search<F>(&self, finished: F) -> Search<usize, usize>
+    where
+        F: Fn(&Self, usize) -> bool,
+    {
+        bfs(
+            [self.end],
+            |&index| {
+                let min_height = self.heights[index].saturating_sub(1);
+                self.heights
+                    .neighbours(index)
+                    .filter(move |&next| min_height <= self.heights[next])
+            },
+            |&index| finished(self, index),
+        )
+    }
+
+    pub fn is_start(&self, index: usize) -> bool {
+        index == self.start
+    }
+
+    pub fn is_height_a(&self, index: usize) -> bool {
+        self.heights[index] == b'a'
+    }
+}
+
+impl FromStr for Map {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut heights = Grid::from_lines(input.lines(), |line| {
+            line.char_indices().map(|(i, c)| match c {
+                'a'..='z' | 'S' | 'E' => Ok(c as u8),
+                _ => Err(with_span(
+                    &line[i..i + c.len_utf8()],
+                    Error::msg("invalid character in height map"),
+                )),
+            })
+        })?;
+        let start = heights
+            .iter()
+            .position(|&c| c == b'S')
+            .context("no start position found")?;
+        heights[start] = b'a';
+        let end = heights
+            .iter()
+            .position(|&c| c == b'E')
+            .context("no end position found")?;
+        heights[end] = b'z';
+        Ok(Self {
+            start,
+            end,
+            heights,
+        })
+    }
+}
+
+/// The search from the end, expanding one distance at a time, with the shortest path drawn once
+/// it has been found.
+#[cfg(feature = "visualize")]
+pub struct Climb {
+    map: Map,
+    search: Search<usize, usize>,
+    distance: usize,
+    max_distance: usize,
+}
+
+#[cfg(feature = "visualize")]
+impl Climb {
+    pub fn new(map: Map, finished: fn(&Map, usize) -> bool) -> Self {
+        let search = map.search(finished);
+        let max_distance = search
+            .goal_distance()
+            .or_else(|| search.distances().values().max().copied())
+            .unwrap_or(0);
+        Self {
+            map,
+            search,
+            distance: 0,
+            max_distance,
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl crate::animation::Animation for Climb {
+    fn step(&mut self) -> Result<bool> {
+        if self.distance == self.max_distance {
+            return Ok(false);
+        }
+        self.distance += 1;
+        Ok(true)
+    }
+
+    /// Draws the heights of the squares not reached yet, with the squares at the current
+    /// distance as `@`, the closer ones as `.` and the path as `#`.
+    fn frame(&self) -> String {
+        let path = match self.search.path() {
+            Some(path) if self.distance == self.max_distance => path,
+            _ => vec![],
+        };
+        let mut s = String::new();
+        for (index, &height) in self.map.heights.iter().enumerate() {
+            s.push(if index == self.map.end {
+                'E'
+            } else if index == self.map.start {
+                'S'
+            } else if path.contains(&index) {
+                '#'
+            } else {
+                match self.search.distance(&index) {
+                    Some(d) if d == self.distance => '@',
+                    Some(d) if d < self.distance => '.',
+                    _ => char::from(height),
+                }
+            });
+            if (index + 1).is_multiple_of(self.map.heights.width()) {
+                s.push('\n');
+            }
+        }
+        s
+    }
+
+    fn caption(&self) -> String {
+        let found = match self.search.goal() {
+            Some(_) if self.distance == self.max_distance => ", path found",
+            Some(_) => "",
+            None => ", no path found",
+        };
+        format!("distance {}{found}", self.distance)
+    }
+}
@@ -0,0 +1,139 @@
+//! Day 13: Distress Signal
+
+use crate::{parse_at, parse_blocks_with, parse_lines_with, with_span, Solution};
+use anyhow::{Context, Error, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{cmp::Ordering, str::FromStr};
+
+/// Compares pairs of distress signal packets and sorts them including the divider packets.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Vec<[Packet; 2]>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        parse_input(input)
+    }
+
+    fn part1(pairs: &Self::Parsed) -> Result<usize> {
+        Ok((1usize..)
+            .zip(pairs)
+            .filter_map(|(i, [left, right])| (left < right).then_some(i))
+            .sum())
+    }
+
+    fn part2(pairs: &Self::Parsed) -> Result<usize> {
+        let mut packets: Vec<&Packet> = pairs.iter().flatten().collect();
+        packets.sort_unstable();
+        Ok((binary_search(&packets, "[[2]]")? + 1) * (binary_search(&packets, "[[6]]")? + 2))
+    }
+}
+
+fn binary_search(packets: &[&Packet], p: &str) -> Result<usize> {
+    packets
+        .binary_search(&&p.parse()?)
+        .err()
+        .context("input contains divider packet")
+}
+
+/// A packet is either an integer or a list of packets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use Packet::*;
+        match (self, other) {
+            (Int(a), Int(b)) => a.cmp(b),
+            (List(v), List(w)) => v.cmp(w),
+            (&Int(a), List(w)) => vec![Packet::Int(a)].cmp(w),
+            (List(v), &Int(b)) => v.cmp(&vec![Packet::Int(b)]),
+        }
+    }
+}
+
+/// The deepest nesting of lists accepted by the parser. Comparing and dropping packets recurses
+/// into the lists, so much deeper packets could overflow the stack.
+pub const MAX_DEPTH: usize = 1000;
+
+impl FromStr for Packet {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        use ParserState::*;
+        let mut stack = vec![];
+        let mut current = vec![];
+        let mut state = StartItem;
+        for token in tokens(line) {
+            match (state, token) {
+                (StartItem, "[") if stack.len() == MAX_DEPTH => {
+                    return Err(with_span(
+                        token,
+                        Error::msg(format!("packet nested more than {MAX_DEPTH} levels deep")),
+                    ))
+                }
+                (StartItem, "[") => stack.push(std::mem::take(&mut current)),
+                (_, "]") => {
+                    let mut outer = stack
+                        .pop()
+                        .ok_or_else(|| with_span(token, Error::msg("unexpected ]")))?;
+                    outer.push(Packet::List(current));
+                    current = outer;
+                    state = Finished;
+                }
+                (StartItem, _) => {
+                    current.push(Packet::Int(parse_at(token)?));
+                    state = Finished;
+                }
+                (Finished, ",") => state = StartItem,
+                _ => return Err(with_span(token, Error::msg("unexpected token"))),
+            }
+        }
+        if stack.is_empty() && current.len() == 1 {
+            Ok(current.pop().unwrap())
+        } else {
+            Err(Error::msg("parse error"))
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ParserState {
+    StartItem,
+    Finished,
+}
+
+fn tokens(mut line: &str) -> impl Iterator<Item = &str> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"^(?:\d+|.)").unwrap();
+    }
+    std::iter::from_fn(move || {
+        let m = RE.find(line)?;
+        line = &line[m.end()..];
+        Some(m.as_str())
+    })
+}
+
+/// Parses the blocks of packet pairs.
+fn parse_input(input: &str) -> Result<Vec<[Packet; 2]>> {
+    parse_blocks_with(input, |block| {
+        let packets = parse_lines_with(block.text, parse_at).collect::<Result<Vec<Packet>>>()?;
+        let count = packets.len();
+        packets
+            .try_into()
+            .map_err(|_| Error::msg(format!("expected a pair of packets, found {count}")))
+    })
+    .collect()
+}
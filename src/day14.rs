@@ -0,0 +1,258 @@
+//! Day 14: Regolith Reservoir
+
+use crate::{array_windows, parse_at, parse_lines, with_span, Solution, SparseGrid};
+use anyhow::{Error, Result};
+use std::{fmt::Display, str::FromStr};
+
+const SOURCE_X: i64 = 500;
+
+/// How far rock may be below or to either side of the source. Rock is drawn cell by cell, and
+/// the sand on the floor spreads as far as the floor is deep, so distant rock would take too long.
+pub const MAX_DISTANCE: i64 = 1000;
+
+/// Counts the units of sand that come to rest before flowing into the void, and with a floor.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Map;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {
+        let paths = parse_lines(input).collect::<Result<Vec<Path>>>()?;
+        Map::new(&paths)
+    }
+
+    fn part1(map: &Self::Parsed) -> Result<u32> {
+        let (result, count) = map.clone().trickle();
+        if result != TrickleResult::Void {
+            return Err(Error::msg("no sand reached the void"));
+        }
+        Ok(count)
+    }
+
+    fn part2(map: &Self::Parsed) -> Result<u32> {
+        let mut map = map.clone();
+        map.floor = true;
+        let (result, count) = map.trickle();
+        if result != TrickleResult::Blocked {
+            return Err(Error::msg("the source did not become blocked"));
+        }
+        Ok(count)
+    }
+}
+
+/// The rock and sand in the cave, and optionally an infinite floor two units below the lowest
+/// rock.
+#[derive(Clone)]
+pub struct Map {
+    tiles: SparseGrid<Tile>,
+    floor_y: i64,
+    pub floor: bool,
+}
+
+impl Map {
+    pub fn new(paths: &[Path]) -> Result<Self> {
+        let mut map = Map {
+            tiles: SparseGrid::new(),
+            floor_y: 0,
+            floor: false,
+        };
+        for p in paths {
+            for coords in array_windows(&p.nodes) {
+                map.draw_rock_line(coords)?;
+            }
+        }
+        map.floor_y = map.tiles.bounds().map_or(0, |b| b.max_y) + 2;
+        Ok(map)
+    }
+
+    /// Fills a horizontal or vertical line with rock.
+    pub fn draw_rock_line(&mut self, coords: &[(i64, i64); 2]) -> Result<()> {
+        let &[(x0, y0), (x1, y1)] = coords;
+        for (x, y) in [(x0, y0), (x1, y1)] {
+            if y < 0 {
+                return Err(Error::msg(format!("rock at {x},{y} is above the source")));
+            }
+            if y > MAX_DISTANCE {
+                return Err(Error::msg(format!(
+                    "rock too deep at {x},{y}, expected at most {MAX_DISTANCE} below the source"
+                )));
+            }
+            if x.abs_diff(SOURCE_X) > MAX_DISTANCE as u64 {
+                return Err(Error::msg(format!(
+                    "rock too far out at {x},{y}, expected at most {MAX_DISTANCE} beside the source"
+                )));
+            }
+        }
+        if x0 == x1 {
+            for y in y0.min(y1)..=y0.max(y1) {
+                self.tiles.insert(x0, y, Tile::Rock);
+            }
+            Ok(())
+        } else if y0 == y1 {
+            for x in x0.min(x1)..=x0.max(x1) {
+                self.tiles.insert(x, y0, Tile::Rock);
+            }
+            Ok(())
+        } else {
+            Err(Error::msg(
+                "only horizontal and vertical lines are supported",
+            ))
+        }
+    }
+
+    /// The rock and the sand that came to rest.
+    pub fn tiles(&self) -> &SparseGrid<Tile> {
+        &self.tiles
+    }
+
+    /// The height of the floor, which is only there if `floor` is set.
+    pub fn floor_y(&self) -> i64 {
+        self.floor_y
+    }
+
+    fn is_blocked(&self, x: i64, y: i64) -> bool {
+        (self.floor && y == self.floor_y) || self.tiles.contains(x, y)
+    }
+
+    /// Drops a single unit of sand from the source.
+    pub fn trickle_one(&mut self) -> TrickleResult {
+        let mut x = SOURCE_X;
+        let mut y = 0;
+        if self.is_blocked(x, y) {
+            return TrickleResult::Blocked;
+        }
+        'outer: while y < self.floor_y {
+            let new_y = y + 1;
+            for new_x in [x, x - 1, x + 1] {
+                if !self.is_blocked(new_x, new_y) {
+                    x = new_x;
+                    y = new_y;
+                    continue 'outer;
+                }
+            }
+            self.tiles.insert(x, y, Tile::Sand);
+            return TrickleResult::Rest;
+        }
+        TrickleResult::Void
+    }
+
+    /// Drops sand until a unit falls into the void or the source is blocked, and returns the
+    /// number of units that came to rest.
+    pub fn trickle(&mut self) -> (TrickleResult, u32) {
+        let mut result;
+        let mut count = 0;
+        loop {
+            result = self.trickle_one();
+            match result {
+                TrickleResult::Rest => count += 1,
+                TrickleResult::Void => break,
+                TrickleResult::Blocked => break,
+            }
+        }
+        (result, count)
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rendered = self.tiles.render(|tile| match tile {
+            None => ' ',
+            Some(Tile::Rock) => '█',
+            Some(Tile::Sand) => 'o',
+        });
+        rendered.fmt(f)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TrickleResult {
+    Rest,
+    Void,
+    Blocked,
+}
+
+/// A path of rock, parsed from `x,y -> x,y -> ...`.
+pub struct Path {
+    pub nodes: Vec<(i64, i64)>,
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let nodes = line
+            .split(" -> ")
+            .map(|s| {
+                let (x, y) = s
+                    .split_once(',')
+                    .ok_or_else(|| with_span(s, Error::msg("invalid coordinates")))?;
+                Ok((parse_at(x)?, parse_at(y)?))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { nodes })
+    }
+}
+
+/// The sand falling from the source, one unit at a time.
+#[cfg(feature = "visualize")]
+pub struct SandFall {
+    map: Map,
+    count: u32,
+    result: TrickleResult,
+}
+
+#[cfg(feature = "visualize")]
+impl SandFall {
+    pub fn new(map: Map) -> Self {
+        Self {
+            map,
+            count: 0,
+            result: TrickleResult::Rest,
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl crate::animation::Animation for SandFall {
+    fn step(&mut self) -> Result<bool> {
+        if self.result != TrickleResult::Rest {
+            return Ok(false);
+        }
+        self.result = self.map.trickle_one();
+        if self.result == TrickleResult::Rest {
+            self.count += 1;
+        }
+        Ok(true)
+    }
+
+    fn frame(&self) -> String {
+        let mut s = self.map.to_string();
+        if let (true, Some(bounds)) = (self.map.floor, self.map.tiles.bounds()) {
+            let width = bounds.width() as usize;
+            for _ in bounds.max_y + 1..self.map.floor_y {
+                s.push_str(&" ".repeat(width));
+                s.push('\n');
+            }
+            s.push_str(&"█".repeat(width));
+            s.push('\n');
+        }
+        s
+    }
+
+    fn caption(&self) -> String {
+        let state = match self.result {
+            TrickleResult::Rest => "",
+            TrickleResult::Void => ", the sand flows into the void",
+            TrickleResult::Blocked => ", the source is blocked",
+        };
+        format!("{} units of sand at rest{state}", self.count)
+    }
+}
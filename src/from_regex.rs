@@ -0,0 +1,148 @@
+//! The `from_regex!` macro, which implements `FromStr` by matching a regular expression.
+
+use crate::with_span;
+use anyhow::{Context, Error, Result};
+use regex::{Captures, Regex};
+
+/// Implements `FromStr` for a struct by matching a regular expression against the whole string
+/// and filling each field from the capture group of the same name.
+///
+/// Each field is given in one of three forms:
+///
+/// - `name` parses the group `name` with `FromStr`,
+/// - `name: parser` passes the text of the group `name` to `parser`, which returns a `Result`,
+/// - `name = value` sets the field to `value` without consulting the captures.
+///
+/// Errors name the field that failed and point at the text of its group.
+///
+/// ```
+/// use aoc2022::from_regex;
+///
+/// struct Move {
+///     count: usize,
+///     to: char,
+///     done: bool,
+/// }
+///
+/// from_regex!(Move, r"move (?P<count>\d+) to (?P<to>.)", { count, to, done = false });
+///
+/// let m: Move = "move 3 to x".parse().unwrap();
+/// assert_eq!((m.count, m.to, m.done), (3, 'x', false));
+/// ```
+#[macro_export]
+macro_rules! from_regex {
+    ($ty:ty, $pattern:expr, { $($fields:tt)* }) => {
+        impl ::std::str::FromStr for $ty {
+            type Err = $crate::__anyhow::Error;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                static RE: ::std::sync::OnceLock<$crate::__regex::Regex> =
+                    ::std::sync::OnceLock::new();
+                let re = RE.get_or_init(|| {
+                    $crate::__regex::Regex::new(&format!("^(?:{})$", $pattern)).unwrap()
+                });
+                let cap = $crate::regex_captures(re, s, stringify!($ty))?;
+                Ok($crate::from_regex!(@fields cap [] $($fields)*))
+            }
+        }
+    };
+    (@fields $cap:ident [$($done:tt)*]) => {
+        Self { $($done)* }
+    };
+    (@fields $cap:ident [$($done:tt)*] $field:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::from_regex!(@fields $cap [$($done)* $field: $value,] $($($rest)*)?)
+    };
+    (@fields $cap:ident [$($done:tt)*] $field:ident : $parser:expr $(, $($rest:tt)*)?) => {
+        $crate::from_regex!(@fields $cap [
+            $($done)* $field: $crate::regex_field(&$cap, stringify!($field), $parser)?,
+        ] $($($rest)*)?)
+    };
+    (@fields $cap:ident [$($done:tt)*] $field:ident $(, $($rest:tt)*)?) => {
+        $crate::from_regex!(@fields $cap [
+            $($done)* $field: $crate::regex_field(&$cap, stringify!($field), $crate::parse_at)?,
+        ] $($($rest)*)?)
+    };
+}
+
+#[doc(hidden)]
+pub fn regex_captures<'a>(re: &Regex, s: &'a str, name: &str) -> Result<Captures<'a>> {
+    re.captures(s)
+        .ok_or_else(|| with_span(s, Error::msg(format!("invalid {name}"))))
+}
+
+#[doc(hidden)]
+pub fn regex_field<'a, T>(
+    cap: &Captures<'a>,
+    field: &str,
+    parse: impl FnOnce(&'a str) -> Result<T>,
+) -> Result<T> {
+    let text = cap
+        .name(field)
+        .with_context(|| format!("no capture group for {field}"))?
+        .as_str();
+    parse(text).map_err(|e| with_span(text, e.context(format!("invalid {field}"))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{locate_parse_error, parse_split_comma, ParseError};
+
+    #[derive(Debug, PartialEq)]
+    struct Line {
+        name: String,
+        values: Vec<u32>,
+        scale: u8,
+        seen: bool,
+    }
+
+    from_regex!(
+        Line,
+        r"(?P<name>\w+): (?P<values>[\d, ]*) x(?P<scale>\d+)",
+        {
+            name,
+            values: |s| parse_split_comma(s).collect(),
+            scale,
+            seen = false,
+        }
+    );
+
+    #[test]
+    fn fields() {
+        let line: Line = "abc: 1, 2 x3".parse().unwrap();
+        assert_eq!(
+            line,
+            Line {
+                name: "abc".into(),
+                values: vec![1, 2],
+                scale: 3,
+                seen: false,
+            }
+        );
+    }
+
+    #[test]
+    fn errors_name_the_field() {
+        let input = "abc: 1 x3\nabc: 1 x300";
+        let error = input.lines().nth(1).unwrap().parse::<Line>().unwrap_err();
+        let error = locate_parse_error(input, error);
+        assert_eq!(
+            error.downcast_ref::<ParseError>().unwrap().position(),
+            Some((2, 9))
+        );
+        assert_eq!(
+            format!("{error:#}"),
+            "invalid scale: number too large to fit in target type at line 2, column 9: \"300\""
+        );
+
+        let error = "abc: 1,,2 x3".parse::<Line>().unwrap_err();
+        assert!(error.to_string().starts_with("invalid values"));
+        let error = "abc 1 x3".parse::<Line>().unwrap_err();
+        assert_eq!(error.to_string(), "invalid Line: \"abc 1 x3\"");
+    }
+
+    #[test]
+    fn whole_string_must_match() {
+        assert!("abc: 1 x3 ".parse::<Line>().is_err());
+        assert!(" abc: 1 x3".parse::<Line>().is_err());
+    }
+}
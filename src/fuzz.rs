@@ -0,0 +1,240 @@
+//! Fuzzing of the solutions with generated inputs.
+//!
+//! [`check`] solves an input, turning panics into errors, and compares the answers with those of
+//! simpler, slower reference implementations where there are any. [`fuzz`] checks the inputs
+//! generated from a range of seeds, see [`generate`](crate::generate).
+
+use crate::{
+    answers::Answers,
+    day12::{self, Map},
+    day13::Packet,
+    day14::{self, Tile},
+    generate::generate,
+    search::dijkstra,
+    Puzzle, Solution,
+};
+use anyhow::{Context, Error, Result};
+use std::{
+    any::Any,
+    collections::HashSet,
+    ops::Range,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+/// Solves `input`, failing if the solution panics or returns an error, or if its answers differ
+/// from the reference implementation.
+pub fn check(puzzle: &Puzzle, input: &str) -> Result<[String; 2]> {
+    let answers = catch_unwind(AssertUnwindSafe(|| puzzle.solve(input)))
+        .map_err(|payload| Error::msg(format!("panicked: {}", panic_message(&*payload))))??;
+    let expected =
+        reference_answers(puzzle.day(), input).context("reference implementation failed")?;
+    for (part, expected) in expected {
+        let answer = &answers[usize::from(part - 1)];
+        if *answer != expected {
+            return Err(Error::msg(format!(
+                "part {part} answered {answer}, but the reference implementation found {expected}"
+            )));
+        }
+    }
+    Ok(answers)
+}
+
+/// Checks the inputs generated from each of `seeds` at `scale`.
+///
+/// Errors name the seed, so that the input can be generated again, e.g. with `aoc generate`.
+pub fn fuzz(puzzle: &Puzzle, seeds: Range<u64>, scale: usize) -> Result<()> {
+    let day = puzzle.day();
+    for seed in seeds {
+        let input = generate(day, seed, scale)?;
+        check(puzzle, &input)
+            .with_context(|| format!("day {day} failed with seed {seed} at scale {scale}"))?;
+    }
+    Ok(())
+}
+
+fn panic_message(payload: &dyn Any) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic payload"
+    }
+}
+
+/// The answers of the reference implementations for `day`, which exist for some parts of some
+/// days only.
+pub fn reference_answers(day: u8, input: &str) -> Result<Answers> {
+    let answers = match day {
+        1 => day01(input)?,
+        6 => day06(input)?,
+        12 => day12(input)?,
+        13 => day13(input)?,
+        14 => day14(input)?,
+        _ => vec![],
+    };
+    Ok(answers
+        .into_iter()
+        .map(|(part, answer)| (part, answer.to_string()))
+        .collect())
+}
+
+/// Sums the blocks without the library parsers, and sorts all of them.
+fn day01(input: &str) -> Result<Vec<(u8, usize)>> {
+    let mut totals = input
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|block| block.lines().map(str::parse::<usize>).sum())
+        .collect::<Result<Vec<usize>, _>>()?;
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    let top3 = totals.get(..3).context("fewer than three elves")?;
+    Ok(vec![(1, totals[0]), (2, top3.iter().sum())])
+}
+
+/// Checks every window for duplicates.
+fn day06(input: &str) -> Result<Vec<(u8, usize)>> {
+    let packet = input.trim().as_bytes();
+    let start = |len: usize| {
+        packet
+            .windows(len)
+            .position(|w| w.iter().collect::<HashSet<_>>().len() == len)
+            .map(|i| i + len)
+            .context("no start marker found")
+    };
+    Ok(vec![(1, start(4)?), (2, start(14)?)])
+}
+
+/// Searches forwards from the start, or from all squares of height `a`, with Dijkstra's
+/// algorithm instead of a backward breadth-first search.
+fn day12(input: &str) -> Result<Vec<(u8, usize)>> {
+    let map: Map = day12::Day12::parse(input)?;
+    let heights = &map.heights;
+    let distance = |sources: Vec<usize>| {
+        dijkstra(
+            sources,
+            |&index| {
+                let max_height = heights[index] + 1;
+                heights
+                    .neighbours(index)
+                    .filter(move |&next| heights[next] <= max_height)
+            },
+            |_, _| 1,
+            |&index| index == map.end,
+        )
+        .goal_distance()
+        .context("no path to target found")
+    };
+    let lowest = (heights.iter().enumerate())
+        .filter_map(|(i, &height)| (height == b'a').then_some(i))
+        .collect();
+    Ok(vec![
+        (1, distance(vec![map.start])?),
+        (2, distance(lowest)?),
+    ])
+}
+
+/// Counts the packets before each divider instead of sorting.
+fn day13(input: &str) -> Result<Vec<(u8, usize)>> {
+    let pairs = crate::day13::Day13::parse(input)?;
+    let before = |divider: &str| -> Result<usize> {
+        let divider: Packet = divider.parse()?;
+        Ok(pairs.iter().flatten().filter(|&p| *p < divider).count())
+    };
+    Ok(vec![(2, (before("[[2]]")? + 1) * (before("[[6]]")? + 2))])
+}
+
+/// With the floor, sand comes to rest on every square that can be reached from the source by
+/// moving down, down-left or down-right, so the squares are filled row by row.
+fn day14(input: &str) -> Result<Vec<(u8, usize)>> {
+    let map = day14::Day14::parse(input)?;
+    let is_rock = |x: i64, y: i64| map.tiles().get(x, y) == Some(&Tile::Rock);
+    let mut row: HashSet<i64> = [500].into_iter().filter(|&x| !is_rock(x, 0)).collect();
+    let mut count = row.len();
+    for y in 1..map.floor_y() {
+        row = row
+            .iter()
+            .flat_map(|&x| [x - 1, x, x + 1])
+            .filter(|&x| !is_rock(x, y))
+            .collect();
+        count += row.len();
+    }
+    Ok(vec![(2, count)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{example_path, puzzle};
+    use std::fs;
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Parsed = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(_input: &str) -> Result<Self::Parsed> {
+            Ok(vec![])
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<u32> {
+            Ok(parsed[0])
+        }
+
+        fn part2(_parsed: &Self::Parsed) -> Result<u32> {
+            Ok(0)
+        }
+    }
+
+    /// Answers the length of the datastream instead of the positions of the markers.
+    struct Wrong;
+
+    impl Solution for Wrong {
+        type Parsed = usize;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Parsed> {
+            Ok(input.trim().len())
+        }
+
+        fn part1(len: &Self::Parsed) -> Result<usize> {
+            Ok(*len)
+        }
+
+        fn part2(len: &Self::Parsed) -> Result<usize> {
+            Ok(*len)
+        }
+    }
+
+    #[test]
+    fn failures() {
+        let error = check(&Puzzle::new::<Panics>(1), "").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("panicked: index out of bounds"));
+
+        let error = fuzz(&Puzzle::new::<Wrong>(6), 0..10, 20).unwrap_err();
+        assert!(format!("{error:#}").starts_with("day 6 failed with seed 0 at scale 20: part 1"));
+    }
+
+    #[test]
+    fn references_solve_examples() {
+        for (day, expected) in [
+            (1, vec![(1, "24000"), (2, "45000")]),
+            (6, vec![(1, "7"), (2, "19")]),
+            (12, vec![(1, "31"), (2, "29")]),
+            (13, vec![(2, "140")]),
+            (14, vec![(2, "93")]),
+        ] {
+            let input = fs::read_to_string(example_path(&format!("day{day:02}"), 1)).unwrap();
+            let expected: Answers = expected
+                .into_iter()
+                .map(|(part, answer)| (part, answer.to_owned()))
+                .collect();
+            assert_eq!(reference_answers(day, &input).unwrap(), expected);
+            check(puzzle(day).unwrap(), &input).unwrap();
+        }
+    }
+}
@@ -0,0 +1,556 @@
+//! Random puzzle inputs, for stress testing and fuzzing the solutions.
+//!
+//! [`generate`] makes a valid input for a day from a seed, so that any input can be reproduced
+//! from its day, seed and scale. The scale is roughly the number of lines or items of the input,
+//! e.g. the number of elves on day 1 or the width of the height map on day 12. Apart from being
+//! well-formed, generated inputs have an answer for both parts, as real inputs do.
+
+use crate::{
+    day13::{Packet, MAX_DEPTH},
+    day14::{self, TrickleResult},
+    Solution,
+};
+use anyhow::{Error, Result};
+use std::{cmp::Ordering, ops::RangeInclusive};
+
+/// A small pseudo-random number generator (SplitMix64), which is good enough for test inputs and
+/// gives the same numbers on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range {start}..={end}");
+        let span = (end - start) as u64;
+        if span == u64::MAX {
+            return self.next_u64() as usize;
+        }
+        start + (self.next_u64() % (span + 1)) as usize
+    }
+
+    /// A number below `n`, which must be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        self.range(0..=n - 1)
+    }
+
+    /// Returns `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    /// A random element of `items`, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+/// The days [`generate`] can make inputs for.
+pub const DAYS: RangeInclusive<u8> = 1..=14;
+
+/// Makes a random input for `day` from `seed`, with a size depending on `scale`.
+pub fn generate(day: u8, seed: u64, scale: usize) -> Result<String> {
+    let rng = &mut Rng::new(seed);
+    let scale = scale.max(1);
+    let input = match day {
+        1 => day01(rng, scale),
+        2 => day02(rng, scale),
+        3 => day03(rng, scale),
+        4 => day04(rng, scale),
+        5 => day05(rng, scale),
+        6 => day06(rng, scale),
+        7 => day07(rng, scale),
+        8 => day08(rng, scale),
+        9 => day09(rng, scale),
+        10 => day10(rng, scale),
+        11 => day11(rng, scale),
+        12 => day12(rng, scale),
+        13 => day13(rng, scale),
+        14 => day14(rng, scale),
+        _ => return Err(Error::msg(format!("no input generator for day {day}"))),
+    };
+    Ok(input)
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Calorie counts of `scale` elves, carrying up to eight items each.
+fn day01(rng: &mut Rng, scale: usize) -> String {
+    let elves: Vec<String> = (0..scale)
+        .map(|_| {
+            let items: Vec<String> = (0..rng.range(1..=8))
+                .map(|_| rng.range(1000..=60_000).to_string())
+                .collect();
+            items.join("\n") + "\n"
+        })
+        .collect();
+    elves.join("\n")
+}
+
+/// `scale` rounds of rock paper scissors.
+fn day02(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                *rng.choose(b"ABC") as char,
+                *rng.choose(b"XYZ") as char
+            )
+        })
+        .collect()
+}
+
+/// Groups of three rucksacks sharing exactly one badge, where the compartments of each rucksack
+/// share exactly one item.
+fn day03(rng: &mut Rng, scale: usize) -> String {
+    let letters: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+    let mut input = String::new();
+    for _ in 0..scale.div_ceil(3) {
+        let mut others = letters.clone();
+        rng.shuffle(&mut others);
+        let badge = others.pop().unwrap();
+        // Each rucksack leaves out a third of the other letters, so no other letter is in all
+        // three.
+        let thirds: Vec<&[u8]> = others.chunks(others.len().div_ceil(3)).collect();
+        for k in 0..3 {
+            let mut allowed: Vec<u8> = (0..3)
+                .filter(|&j| j != k)
+                .flat_map(|j| thirds[j].iter().copied())
+                .collect();
+            rng.shuffle(&mut allowed);
+            let shared = allowed.pop().unwrap();
+            let (first_pool, second_pool) = allowed.split_at(allowed.len() / 2);
+            let len = rng.range(2..=12);
+            let mut first = vec![shared, badge];
+            let mut second = vec![shared];
+            first.extend((2..len).map(|_| *rng.choose(first_pool)));
+            second.extend((1..len).map(|_| *rng.choose(second_pool)));
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+            input.extend(first.iter().chain(&second).map(|&c| c as char));
+            input.push('\n');
+        }
+    }
+    input
+}
+
+/// `scale` pairs of section assignments.
+fn day04(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let a = rng.range(1..=99);
+            let b = rng.range(a..=99);
+            let c = rng.range(1..=99);
+            let d = rng.range(c..=99);
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
+
+/// Up to nine stacks, some of which may be empty, and `scale` steps that never take more crates
+/// than a stack holds.
+fn day05(rng: &mut Rng, scale: usize) -> String {
+    let count = rng.range(2..=9);
+    let mut heights: Vec<usize> = (0..count).map(|_| rng.range(0..=scale.min(20))).collect();
+    heights[0] = heights[0].max(1);
+    let max_height = *heights.iter().max().unwrap();
+    let mut input = String::new();
+    for y in (0..max_height).rev() {
+        let row: Vec<String> = heights
+            .iter()
+            .map(|&h| {
+                if y < h {
+                    format!(
+                        "[{}]",
+                        rng.range(b'A' as usize..=b'Z' as usize) as u8 as char
+                    )
+                } else {
+                    "   ".to_owned()
+                }
+            })
+            .collect();
+        input.push_str(row.join(" ").trim_end());
+        input.push('\n');
+    }
+    let labels: Vec<String> = (1..=count).map(|l| format!(" {l} ")).collect();
+    input.push_str(&labels.join(" "));
+    input.push_str("\n\n");
+    for _ in 0..scale {
+        let sources: Vec<usize> = (0..count).filter(|&i| heights[i] > 0).collect();
+        let from = *rng.choose(&sources);
+        let to = (from + rng.range(1..=count - 1)) % count;
+        let moved = rng.range(1..=heights[from]);
+        heights[from] -= moved;
+        heights[to] += moved;
+        input.push_str(&format!("move {moved} from {} to {}\n", from + 1, to + 1));
+    }
+    input
+}
+
+/// A datastream of about `scale` characters from a random number of letters, so that markers may
+/// be rare, with a start-of-message marker inserted anywhere, including at the very end.
+fn day06(rng: &mut Rng, scale: usize) -> String {
+    let letters = &LOWERCASE[..rng.range(1..=26)];
+    let mut stream: Vec<u8> = (0..scale).map(|_| *rng.choose(letters)).collect();
+    let mut marker = LOWERCASE.to_vec();
+    rng.shuffle(&mut marker);
+    let at = rng.range(0..=stream.len());
+    stream.splice(at..at, marker[..14].iter().copied());
+    String::from_utf8(stream).unwrap() + "\n"
+}
+
+struct Directory {
+    files: Vec<(String, usize)>,
+    dirs: Vec<(String, Directory)>,
+}
+
+impl Directory {
+    fn size(&self) -> usize {
+        let files: usize = self.files.iter().map(|(_, size)| size).sum();
+        files + self.dirs.iter().map(|(_, dir)| dir.size()).sum::<usize>()
+    }
+
+    /// The names of the files and subdirectories.
+    fn names(&self) -> Vec<String> {
+        let files = self.files.iter().map(|(name, _)| name.clone());
+        files
+            .chain(self.dirs.iter().map(|(name, _)| name.clone()))
+            .collect()
+    }
+
+    /// Appends the commands listing this directory and, recursively, its subdirectories.
+    fn session(&self, session: &mut String) {
+        session.push_str("$ ls\n");
+        for (name, _) in &self.dirs {
+            session.push_str(&format!("dir {name}\n"));
+        }
+        for (name, size) in &self.files {
+            session.push_str(&format!("{size} {name}\n"));
+        }
+        for (name, dir) in &self.dirs {
+            session.push_str(&format!("$ cd {name}\n"));
+            dir.session(session);
+            session.push_str("$ cd ..\n");
+        }
+    }
+}
+
+/// A terminal session exploring about `scale` directories, nested up to `scale` deep, with file
+/// sizes spread over several orders of magnitude and more than 40000000 used in total.
+fn day07(rng: &mut Rng, scale: usize) -> String {
+    fn name(rng: &mut Rng, taken: &[String]) -> String {
+        loop {
+            let name: String = (0..rng.range(1..=8))
+                .map(|_| *rng.choose(LOWERCASE) as char)
+                .collect();
+            let name = if rng.chance(0.3) {
+                format!("{name}.{}", rng.choose(&["txt", "dat", "log", "lst"]))
+            } else {
+                name
+            };
+            if !taken.contains(&name) {
+                return name;
+            }
+        }
+    }
+
+    fn directory(rng: &mut Rng, depth: usize, budget: &mut usize) -> Directory {
+        let mut dir = Directory {
+            files: vec![],
+            dirs: vec![],
+        };
+        for _ in 0..rng.range(0..=4) {
+            let name = name(rng, &dir.names());
+            // Sizes spread over several orders of magnitude.
+            let magnitude = rng.range(4..=22);
+            dir.files.push((name, rng.range(1..=1 << magnitude)));
+        }
+        let subdirs = match depth {
+            0 => 0,
+            _ => rng.range(0..=3).min(*budget),
+        };
+        *budget -= subdirs;
+        for _ in 0..subdirs {
+            let name = name(rng, &dir.names());
+            let subdir = directory(rng, depth - 1, budget);
+            dir.dirs.push((name, subdir));
+        }
+        dir
+    }
+
+    // The root takes the directories left over, so that there are `scale` directories in all.
+    let mut budget = scale;
+    let mut root = directory(rng, scale, &mut budget);
+    while budget > 0 {
+        budget -= 1;
+        let name = name(rng, &root.names());
+        let subdir = directory(rng, scale - 1, &mut budget);
+        root.dirs.push((name, subdir));
+    }
+    let used = root.size();
+    if used <= 40_000_000 {
+        let size = 40_000_001 - used + rng.range(0..=20_000_000);
+        root.files.push((name(rng, &root.names()), size));
+    }
+    let mut session = "$ cd /\n".to_owned();
+    root.session(&mut session);
+    session
+}
+
+/// A square forest of `scale` by `scale` trees.
+fn day08(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let row: String = (0..scale)
+                .map(|_| char::from(b'0' + rng.range(0..=9) as u8))
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
+
+/// `scale` motions of up to `scale` steps each.
+fn day09(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&["R", "U", "L", "D"]),
+                rng.range(1..=scale)
+            )
+        })
+        .collect()
+}
+
+/// A program of `scale` instructions.
+fn day10(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            if rng.chance(0.3) {
+                "noop\n".to_owned()
+            } else {
+                format!("addx {}\n", rng.range(0..=40) as i64 - 20)
+            }
+        })
+        .collect()
+}
+
+/// Between 2 and 9 monkeys with distinct prime divisors, holding up to `scale` items each.
+///
+/// Worry levels are kept below the product of the divisors, which is small enough for squaring
+/// them not to overflow.
+fn day11(rng: &mut Rng, scale: usize) -> String {
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+    let count = rng.range(2..=(2 + scale / 5).min(primes.len()));
+    let monkeys: Vec<String> = (0..count)
+        .map(|i| {
+            let items: Vec<String> = (0..rng.range(1..=scale.min(10)))
+                .map(|_| rng.range(1..=99).to_string())
+                .collect();
+            let operation = match rng.below(3) {
+                0 => format!("+ {}", rng.range(1..=8)),
+                1 => format!("* {}", rng.range(2..=19)),
+                _ => "* old".to_owned(),
+            };
+            let mut other = || (i + rng.range(1..=count - 1)) % count;
+            let (if_true, if_false) = (other(), other());
+            format!(
+                "Monkey {i}:\n  \
+                 Starting items: {}\n  \
+                 Operation: new = old {operation}\n  \
+                 Test: divisible by {}\n    \
+                 If true: throw to monkey {if_true}\n    \
+                 If false: throw to monkey {if_false}\n",
+                items.join(", "),
+                primes[i],
+            )
+        })
+        .collect();
+    monkeys.join("\n")
+}
+
+/// A height map at least `scale` wide, with random heights except along a winding path from
+/// the start to the end that never climbs more than one step.
+fn day12(rng: &mut Rng, scale: usize) -> String {
+    let width = scale.max(10);
+    let height = (scale / 2).max(5);
+    let mut map: Vec<Vec<u8>> = (0..height)
+        .map(|_| (0..width).map(|_| *rng.choose(LOWERCASE)).collect())
+        .collect();
+    // The path runs along the even rows, alternately to the right and to the left, and down at
+    // the end of each row.
+    let mut path = vec![];
+    for y in (0..height).step_by(2) {
+        let row: Vec<(usize, usize)> = (0..width).map(|x| (x, y)).collect();
+        match (y / 2) % 2 {
+            0 => path.extend(row),
+            _ => path.extend(row.into_iter().rev()),
+        }
+        if y + 1 < height - 1 {
+            let &(x, _) = path.last().unwrap();
+            path.push((x, y + 1));
+        }
+    }
+    for (i, &(x, y)) in path.iter().enumerate() {
+        map[y][x] = b'a' + (i * 25 / (path.len() - 1)) as u8;
+    }
+    let (start_x, start_y) = path[0];
+    let (end_x, end_y) = path[path.len() - 1];
+    map[start_y][start_x] = b'S';
+    map[end_y][end_x] = b'E';
+    map.iter()
+        .map(|row| String::from_utf8(row.clone()).unwrap() + "\n")
+        .collect()
+}
+
+/// `scale` pairs of packets, nested up to about `scale / 2` deep but within the
+/// [`MAX_DEPTH`] of the parser, none of which is equal to a divider packet.
+fn day13(rng: &mut Rng, scale: usize) -> String {
+    fn list(rng: &mut Rng, depth: usize) -> String {
+        let items: Vec<String> = (0..rng.range(0..=4))
+            .map(|_| {
+                if depth > 0 && rng.chance(0.3) {
+                    list(rng, depth - 1)
+                } else {
+                    rng.range(0..=10).to_string()
+                }
+            })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+
+    fn packet(rng: &mut Rng, max_depth: usize) -> String {
+        let dividers: [Packet; 2] = ["[[2]]".parse().unwrap(), "[[6]]".parse().unwrap()];
+        loop {
+            let depth = rng.range(0..=max_depth);
+            let mut packet = list(rng, depth);
+            // The list is nested up to `depth + 1` levels deep.
+            let max_wrap = max_depth.min(MAX_DEPTH - depth - 1);
+            if rng.chance(0.1) && max_wrap > 0 {
+                // Occasionally wrap the packet in a long chain of single-item lists.
+                let depth = rng.range(1..=max_wrap);
+                packet = format!("{}{packet}{}", "[".repeat(depth), "]".repeat(depth));
+            }
+            let parsed: Packet = packet.parse().unwrap();
+            if dividers.iter().all(|d| parsed.cmp(d) != Ordering::Equal) {
+                return packet;
+            }
+        }
+    }
+
+    let max_depth = (scale / 2).clamp(1, MAX_DEPTH - 1);
+    let pairs: Vec<String> = (0..scale)
+        .map(|_| format!("{}\n{}\n", packet(rng, max_depth), packet(rng, max_depth)))
+        .collect();
+    pairs.join("\n")
+}
+
+/// `scale` rock paths of horizontal and vertical lines, spread around the source of the sand.
+///
+/// Rock can form closed bowls, which could fill up with sand until the source is blocked, leaving
+/// part 1 without an answer. Such layouts are drawn again.
+fn day14(rng: &mut Rng, scale: usize) -> String {
+    // Leaves room for the steps below within the distance allowed by the parser.
+    let spread = scale.min(day14::MAX_DISTANCE as usize - 100);
+    loop {
+        let input: String = (0..scale)
+            .map(|_| {
+                let mut x = rng.range(500 - spread.min(400)..=500 + spread);
+                let mut y = rng.range(1..=spread + 10);
+                let mut nodes = vec![format!("{x},{y}")];
+                for i in 0..rng.range(1..=4) {
+                    let step = rng.range(0..=20) as i64 - 10;
+                    match i % 2 {
+                        0 => x = (x as i64 + step) as usize,
+                        _ => y = (y as i64 + step).max(1) as usize,
+                    }
+                    nodes.push(format!("{x},{y}"));
+                }
+                nodes.join(" -> ") + "\n"
+            })
+            .collect();
+        let mut map = day14::Day14::parse(&input).expect("generated rock paths are valid");
+        if map.trickle().0 == TrickleResult::Void {
+            return input;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PUZZLES;
+
+    #[test]
+    fn rng() {
+        let mut rng = Rng::new(7);
+        let numbers: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+        assert_eq!(Rng::new(7).next_u64(), numbers[0]);
+        assert!(numbers[0] != numbers[1] && numbers[1] != numbers[2]);
+        assert!((0..1000).all(|_| (3..=5).contains(&rng.range(3..=5))));
+        assert_eq!(rng.range(4..=4), 4);
+
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn deterministic() {
+        for day in DAYS {
+            let input = generate(day, 3, 20).unwrap();
+            assert_eq!(input, generate(day, 3, 20).unwrap(), "day {day}");
+            assert_ne!(input, generate(day, 4, 20).unwrap(), "day {day}");
+        }
+        assert!(generate(25, 0, 10).is_err());
+    }
+
+    #[test]
+    fn day13_depth_limit() {
+        // Above a scale of 2000, packets would be nested deeper than the parser allows.
+        for seed in 0..3 {
+            let input = generate(13, seed, 2100).unwrap();
+            let pairs = crate::day13::Day13::parse(&input).unwrap();
+            assert_eq!(pairs.len(), 2100);
+        }
+    }
+
+    #[test]
+    fn inputs_parse() {
+        for puzzle in PUZZLES {
+            for scale in [0, 1, 5, 50] {
+                for seed in 0..5 {
+                    let input = generate(puzzle.day(), seed, scale).unwrap();
+                    if let Err(e) = puzzle.parse(&input) {
+                        panic!(
+                            "day {} seed {seed} scale {scale}: {e:#}\n{input}",
+                            puzzle.day()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,695 @@
+use crate::with_span;
+use anyhow::{Context, Error, Result};
+use std::{
+    iter::StepBy,
+    ops::{Deref, DerefMut, Range},
+};
+
+/// A position in a `Grid`, with `x` increasing to the right and `y` increasing downwards.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves the point by a signed offset, returning `None` if a coordinate would become negative
+    /// or overflow.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+/// The offsets to the four orthogonal neighbours: up, left, right, down.
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The offsets to all eight neighbours, including the diagonal ones, in row-major order.
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A two-dimensional grid, stored in row-major order.
+///
+/// The grid dereferences to the slice of all cells, so cells can also be addressed by index.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Deref for Grid<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T> DerefMut for Grid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<T: Default> Grid<T> {
+    pub fn new(width: usize, height: usize) -> Self {
+        let mut data = vec![];
+        data.resize_with(width * height, T::default);
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    pub fn from_vec(data: Vec<T>, width: usize) -> Result<Self> {
+        if width == 0 || !data.len().is_multiple_of(width) {
+            return Err(Error::msg(format!(
+                "{} cells do not form rows of width {width}",
+                data.len()
+            )));
+        }
+        let height = data.len() / width;
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn from_lines<'a, I, F, L>(lines: I, mut line_parser: F) -> Result<Self>
+    where
+        I: IntoIterator<Item = &'a str>,
+        F: FnMut(&'a str) -> L,
+        L: Iterator<Item = Result<T>> + 'a,
+    {
+        let mut data = Vec::new();
+        let mut width = None;
+        for line in lines {
+            let start_index = data.len();
+            for item in line_parser(line) {
+                data.push(item.map_err(|e| with_span(line, e))?);
+            }
+            let line_len = data.len() - start_index;
+            if *width.get_or_insert(line_len) != line_len {
+                return Err(with_span(
+                    line,
+                    Error::msg("all lines must have the same length"),
+                ));
+            }
+        }
+        let width = width.filter(|&w| w > 0).context("no data in grid")?;
+        Self::from_vec(data, width)
+    }
+
+    /// Whether the point lies within the grid.
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    /// Whether the signed coordinates lie within the grid.
+    pub fn contains_signed(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && self.contains(Point::new(x as usize, y as usize))
+    }
+
+    /// The index of the point in the underlying slice, or `None` if it lies outside the grid.
+    pub fn index(&self, p: Point) -> Option<usize> {
+        self.contains(p).then(|| self.index_unchecked(p))
+    }
+
+    /// The index of a point known to lie within the grid.
+    ///
+    /// For points outside of the grid, the result is meaningless, and may even be a valid index
+    /// of a different point.
+    pub fn index_unchecked(&self, p: Point) -> usize {
+        debug_assert!(self.contains(p), "{p:?} outside of grid");
+        p.y * self.width + p.x
+    }
+
+    /// The point for an index into the underlying slice, or `None` if the index is out of bounds.
+    pub fn point(&self, index: usize) -> Option<Point> {
+        (index < self.data.len()).then(|| self.point_unchecked(index))
+    }
+
+    /// The point for an index known to be in bounds.
+    pub fn point_unchecked(&self, index: usize) -> Point {
+        debug_assert!(index < self.data.len(), "index {index} out of bounds");
+        Point::new(index % self.width, index / self.width)
+    }
+
+    pub fn coords(&self, index: usize) -> (usize, usize) {
+        let p = self.point_unchecked(index);
+        (p.x, p.y)
+    }
+
+    pub fn at(&self, p: Point) -> Option<&T> {
+        self.index(p).map(|i| &self.data[i])
+    }
+
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index(p).map(|i| &mut self.data[i])
+    }
+
+    pub fn get_xy(&self, x: usize, y: usize) -> Option<&T> {
+        self.at(Point::new(x, y))
+    }
+
+    pub fn get_xy_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.at_mut(Point::new(x, y))
+    }
+
+    /// Moves `p` by a signed offset, returning `None` if the result lies outside the grid.
+    pub fn offset(&self, p: Point, dx: isize, dy: isize) -> Option<Point> {
+        p.offset(dx, dy).filter(|&q| self.contains(q))
+    }
+
+    /// All points of the grid, in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.data[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height).then(|| &mut self.data[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // `chunks_exact` panics for a chunk size of zero, but grids without columns have no data.
+        self.data.chunks_exact(self.width.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [T]> + ExactSizeIterator {
+        self.data.chunks_exact_mut(self.width.max(1))
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> Option<impl DoubleEndedIterator<Item = &T>> {
+        (x < self.width).then(|| self.column_cells(x))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column_cells(x))
+    }
+
+    fn column_cells(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        // Grids without rows have no data, even if they have columns.
+        let cells = self.data.get(x..).unwrap_or(&[]);
+        cells.iter().step_by(self.width)
+    }
+
+    /// The orthogonal neighbours of `p` within the grid.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS4
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(p, dx, dy))
+    }
+
+    /// The orthogonal and diagonal neighbours of `p` within the grid.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(p, dx, dy))
+    }
+
+    /// The indices of the orthogonal neighbours of the cell at index `i`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn neighbours(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..4).filter_map(move |j| match j {
+            0 => i.checked_sub(self.width),
+            1 if i % self.width > 0 => Some(i - 1),
+            2 if (i + 1) % self.width > 0 => Some(i + 1),
+            3 if i + self.width < self.data.len() => Some(i + self.width),
+            _ => None,
+        })
+    }
+
+    /// The points reached by repeatedly moving from `from` by a signed step, excluding `from`
+    /// itself and stopping at the edge of the grid.
+    pub fn ray(&self, from: Point, dx: isize, dy: isize) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(self.offset(from, dx, dy), move |&p| self.offset(p, dx, dy))
+    }
+
+    /// The indices of the cells in row `y`, from left to right.
+    ///
+    /// Unlike `row`, this does not borrow the grid, so the cells can be modified while iterating.
+    pub fn row_indices(&self, y: usize) -> Range<usize> {
+        let start = y.min(self.height) * self.width;
+        start..start + if y < self.height { self.width } else { 0 }
+    }
+
+    /// The indices of the cells in column `x`, from top to bottom.
+    pub fn column_indices(&self, x: usize) -> StepBy<Range<usize>> {
+        let end = if x < self.width { self.data.len() } else { 0 };
+        (x.min(end)..end).step_by(self.width.max(1))
+    }
+
+    /// A borrowed view of the `width` by `height` rectangle with its top left corner at `origin`,
+    /// or `None` if the rectangle does not fit into the grid.
+    pub fn view(&self, origin: Point, width: usize, height: usize) -> Option<View<'_, T>> {
+        let fits = origin.x.checked_add(width)? <= self.width
+            && origin.y.checked_add(height)? <= self.height;
+        fits.then_some(View {
+            grid: self,
+            origin,
+            width,
+            height,
+        })
+    }
+
+    /// Creates a grid by calling `f` for each point, in row-major order.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    /// A grid of the same size with `f` applied to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    fn remap(&self, width: usize, height: usize, f: impl Fn(Point) -> Point) -> Self {
+        Self::from_fn(width, height, |p| {
+            self.data[self.index_unchecked(f(p))].clone()
+        })
+    }
+
+    /// The grid mirrored along its main diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |p| Point::new(p.y, p.x))
+    }
+
+    /// The grid rotated by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height;
+        self.remap(h, self.width, |p| Point::new(p.y, h - 1 - p.x))
+    }
+
+    /// The grid rotated by 90 degrees counterclockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let w = self.width;
+        self.remap(self.height, w, |p| Point::new(w - 1 - p.y, p.x))
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        self.remap(w, self.height, |p| Point::new(w - 1 - p.x, p.y))
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        self.remap(self.width, h, |p| Point::new(p.x, h - 1 - p.y))
+    }
+}
+
+/// A borrowed rectangular part of a `Grid`, with its own coordinates starting at (0, 0).
+#[derive(Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point,
+    width: usize,
+    height: usize,
+}
+
+// Derived implementations would require `T: Clone`.
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The point in the underlying grid corresponding to a point of the view.
+    pub fn grid_point(&self, p: Point) -> Option<Point> {
+        (p.x < self.width && p.y < self.height)
+            .then(|| Point::new(self.origin.x + p.x, self.origin.y + p.y))
+    }
+
+    pub fn at(&self, p: Point) -> Option<&'a T> {
+        self.grid.at(self.grid_point(p)?)
+    }
+
+    pub fn get_xy(&self, x: usize, y: usize) -> Option<&'a T> {
+        self.at(Point::new(x, y))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&'a [T]> {
+        if y >= self.height {
+            return None;
+        }
+        let row = self.grid.row(self.origin.y + y)?;
+        Some(&row[self.origin.x..self.origin.x + self.width])
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [T]> + '_ {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> Option<impl DoubleEndedIterator<Item = &'a T> + '_> {
+        (x < self.width).then(|| (0..self.height).filter_map(move |y| self.get_xy(x, y)))
+    }
+
+    /// All cells of the view, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.rows().flatten()
+    }
+}
+
+impl<T: Clone> View<'_, T> {
+    /// Copies the viewed cells into a new grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |p| self.at(p).unwrap().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 4x3 grid with the cells numbered in row-major order.
+    fn grid() -> Grid<usize> {
+        Grid::from_vec((0..12).collect(), 4).unwrap()
+    }
+
+    fn points(coords: &[(usize, usize)]) -> Vec<Point> {
+        coords.iter().map(|&c| c.into()).collect()
+    }
+
+    #[test]
+    fn dimensions() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (4, 3, 12));
+        let empty = Grid::<u8>::new(0, 5);
+        assert_eq!((empty.width(), empty.height(), empty.len()), (0, 5, 0));
+    }
+
+    #[test]
+    fn from_vec() {
+        assert!(Grid::from_vec(vec![1, 2, 3], 2).is_err());
+        assert!(Grid::from_vec(vec![1, 2, 3], 0).is_err());
+        let grid = Grid::from_vec(vec![1, 2, 3], 3).unwrap();
+        assert_eq!(grid.height(), 1);
+        assert_eq!(grid.into_vec(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn from_lines() {
+        let parse = |line: &'static str| line.bytes().map(Ok);
+        let grid = Grid::from_lines(["ab", "cd", "ef"], parse).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get_xy(1, 2), Some(&b'f'));
+        assert!(Grid::from_lines(["ab", "c"], parse).is_err());
+        assert!(Grid::from_lines([], parse).is_err());
+        assert!(Grid::from_lines(["", ""], parse).is_err());
+    }
+
+    #[test]
+    fn contains() {
+        let grid = grid();
+        assert!(grid.contains(Point::new(0, 0)));
+        assert!(grid.contains(Point::new(3, 2)));
+        assert!(!grid.contains(Point::new(4, 0)));
+        assert!(!grid.contains(Point::new(0, 3)));
+        assert!(grid.contains_signed(3, 2));
+        assert!(!grid.contains_signed(-1, 0));
+        assert!(!grid.contains_signed(0, -1));
+        assert!(!grid.contains_signed(4, 2));
+    }
+
+    #[test]
+    fn index_and_point_are_inverse() {
+        let grid = grid();
+        for (i, p) in grid.points().enumerate() {
+            assert_eq!(grid.index(p), Some(i));
+            assert_eq!(grid.index_unchecked(p), i);
+            assert_eq!(grid.point(i), Some(p));
+            assert_eq!(grid.point_unchecked(i), p);
+            assert_eq!(grid.coords(i), (p.x, p.y));
+            assert_eq!(grid[i], i);
+        }
+        assert_eq!(grid.points().count(), 12);
+    }
+
+    #[test]
+    fn index_out_of_bounds() {
+        let grid = grid();
+        assert_eq!(grid.index(Point::new(1, 2)), Some(9));
+        assert_eq!(grid.index(Point::new(4, 0)), None);
+        assert_eq!(grid.index(Point::new(0, 3)), None);
+        assert_eq!(grid.point(11), Some(Point::new(3, 2)));
+        assert_eq!(grid.point(12), None);
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = grid();
+        assert_eq!(grid.get_xy(2, 1), Some(&6));
+        assert_eq!(grid.at(Point::new(2, 1)), Some(&6));
+        assert_eq!(grid.get_xy(4, 1), None);
+        *grid.get_xy_mut(2, 1).unwrap() = 100;
+        *grid.at_mut(Point::new(0, 2)).unwrap() = 200;
+        assert_eq!(grid[6], 100);
+        assert_eq!(grid[8], 200);
+        assert!(grid.at_mut(Point::new(0, 3)).is_none());
+    }
+
+    #[test]
+    fn offsets() {
+        let grid = grid();
+        let p = Point::new(1, 1);
+        assert_eq!(p.offset(-1, 1), Some(Point::new(0, 2)));
+        assert_eq!(p.offset(-2, 0), None);
+        assert_eq!(Point::new(usize::MAX, 0).offset(1, 0), None);
+        assert_eq!(grid.offset(p, 2, 1), Some(Point::new(3, 2)));
+        assert_eq!(grid.offset(p, 3, 0), None);
+        assert_eq!(grid.offset(p, 0, -2), None);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let mut grid = grid();
+        assert_eq!(grid.row(1), Some(&[4, 5, 6, 7][..]));
+        assert_eq!(grid.row(3), None);
+        let rows: Vec<&[usize]> = grid.rows().collect();
+        assert_eq!(rows, [&[0, 1, 2, 3], &[4, 5, 6, 7], &[8, 9, 10, 11]]);
+        assert_eq!(grid.rows().next_back(), Some(&[8, 9, 10, 11][..]));
+        let column: Vec<_> = grid.column(2).unwrap().copied().collect();
+        assert_eq!(column, [2, 6, 10]);
+        let column: Vec<_> = grid.column(3).unwrap().rev().copied().collect();
+        assert_eq!(column, [11, 7, 3]);
+        assert!(grid.column(4).is_none());
+        let columns: Vec<Vec<usize>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, [[0, 4, 8], [1, 5, 9], [2, 6, 10], [3, 7, 11]]);
+        grid.row_mut(0).unwrap().fill(0);
+        for row in grid.rows_mut() {
+            row[3] = 0;
+        }
+        assert_eq!(grid.into_vec(), [0, 0, 0, 0, 4, 5, 6, 0, 8, 9, 10, 0]);
+    }
+
+    #[test]
+    fn empty_rows_and_columns() {
+        let grid = Grid::<u8>::new(0, 3);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.columns().count(), 0);
+        assert_eq!(grid.points().count(), 0);
+
+        let grid = Grid::<u8>::new(3, 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.column(1).unwrap().count(), 0);
+        assert!(grid.column(3).is_none());
+        assert_eq!(grid.columns().count(), 3);
+        assert!(grid.columns().all(|mut column| column.next().is_none()));
+        assert_eq!(grid.points().count(), 0);
+    }
+
+    #[test]
+    fn neighbours4() {
+        let grid = grid();
+        let n: Vec<Point> = grid.neighbours4(Point::new(1, 1)).collect();
+        assert_eq!(n, points(&[(1, 0), (0, 1), (2, 1), (1, 2)]));
+        let n: Vec<Point> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(n, points(&[(1, 0), (0, 1)]));
+        let n: Vec<Point> = grid.neighbours4(Point::new(3, 2)).collect();
+        assert_eq!(n, points(&[(3, 1), (2, 2)]));
+    }
+
+    #[test]
+    fn neighbours8() {
+        let grid = grid();
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        let n: Vec<Point> = grid.neighbours8(Point::new(0, 0)).collect();
+        assert_eq!(n, points(&[(1, 0), (0, 1), (1, 1)]));
+        let n: Vec<Point> = grid.neighbours8(Point::new(3, 1)).collect();
+        assert_eq!(n, points(&[(2, 0), (3, 0), (2, 1), (2, 2), (3, 2)]));
+    }
+
+    #[test]
+    fn neighbours_by_index_match_points() {
+        let grid = grid();
+        for p in grid.points() {
+            let by_index: Vec<usize> = grid.neighbours(grid.index_unchecked(p)).collect();
+            let by_point: Vec<usize> = grid
+                .neighbours4(p)
+                .map(|q| grid.index_unchecked(q))
+                .collect();
+            assert_eq!(by_index, by_point, "neighbours of {p:?}");
+        }
+    }
+
+    #[test]
+    fn ray() {
+        let grid = grid();
+        let ray: Vec<Point> = grid.ray(Point::new(1, 1), 1, 0).collect();
+        assert_eq!(ray, points(&[(2, 1), (3, 1)]));
+        let ray: Vec<Point> = grid.ray(Point::new(3, 2), -1, -1).collect();
+        assert_eq!(ray, points(&[(2, 1), (1, 0)]));
+        assert_eq!(grid.ray(Point::new(0, 0), 0, -1).count(), 0);
+    }
+
+    #[test]
+    fn row_and_column_indices() {
+        let grid = grid();
+        assert_eq!(grid.row_indices(1).collect::<Vec<_>>(), [4, 5, 6, 7]);
+        assert_eq!(
+            grid.row_indices(2).rev().collect::<Vec<_>>(),
+            [11, 10, 9, 8]
+        );
+        assert_eq!(grid.row_indices(3).count(), 0);
+        assert_eq!(grid.column_indices(1).collect::<Vec<_>>(), [1, 5, 9]);
+        assert_eq!(grid.column_indices(3).rev().collect::<Vec<_>>(), [11, 7, 3]);
+        assert_eq!(grid.column_indices(4).count(), 0);
+        assert_eq!(Grid::<u8>::new(0, 3).column_indices(0).count(), 0);
+    }
+
+    #[test]
+    fn map() {
+        let grid = grid();
+        let doubled = grid.map(|&v| v * 2);
+        assert_eq!((doubled.width(), doubled.height()), (4, 3));
+        assert!(doubled.iter().zip(grid.iter()).all(|(&d, &v)| d == v * 2));
+    }
+
+    #[test]
+    fn transformations() {
+        let grid = grid();
+        let rows = |g: &Grid<usize>| g.rows().map(<[usize]>::to_vec).collect::<Vec<_>>();
+        assert_eq!(
+            rows(&grid.transpose()),
+            [[0, 4, 8], [1, 5, 9], [2, 6, 10], [3, 7, 11]]
+        );
+        assert_eq!(
+            rows(&grid.rotate_cw()),
+            [[8, 4, 0], [9, 5, 1], [10, 6, 2], [11, 7, 3]]
+        );
+        assert_eq!(
+            rows(&grid.rotate_ccw()),
+            [[3, 7, 11], [2, 6, 10], [1, 5, 9], [0, 4, 8]]
+        );
+        assert_eq!(
+            rows(&grid.flip_horizontal()),
+            [[3, 2, 1, 0], [7, 6, 5, 4], [11, 10, 9, 8]]
+        );
+        assert_eq!(
+            rows(&grid.flip_vertical()),
+            [[8, 9, 10, 11], [4, 5, 6, 7], [0, 1, 2, 3]]
+        );
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(
+            grid.rotate_cw().rotate_cw(),
+            grid.flip_horizontal().flip_vertical()
+        );
+        let empty = Grid::<u8>::new(0, 3).rotate_cw();
+        assert_eq!((empty.width(), empty.height()), (3, 0));
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid();
+        let view = grid.view(Point::new(1, 1), 3, 2).unwrap();
+        assert_eq!((view.width(), view.height()), (3, 2));
+        assert_eq!(view.get_xy(0, 0), Some(&5));
+        assert_eq!(view.get_xy(2, 1), Some(&11));
+        assert_eq!(view.get_xy(3, 0), None);
+        assert_eq!(view.grid_point(Point::new(1, 1)), Some(Point::new(2, 2)));
+        assert_eq!(view.row(1), Some(&[9, 10, 11][..]));
+        assert_eq!(view.row(2), None);
+        assert_eq!(view.row(usize::MAX), None);
+        let column: Vec<_> = view.column(1).unwrap().rev().copied().collect();
+        assert_eq!(column, [10, 6]);
+        assert_eq!(
+            view.iter().copied().collect::<Vec<_>>(),
+            [5, 6, 7, 9, 10, 11]
+        );
+        assert_eq!(view.to_grid().into_vec(), [5, 6, 7, 9, 10, 11]);
+        assert!(grid.view(Point::new(1, 1), 4, 1).is_none());
+        assert!(grid.view(Point::new(0, 2), 1, 2).is_none());
+        assert_eq!(grid.view(Point::new(0, 0), 4, 3).unwrap().to_grid(), grid);
+    }
+}
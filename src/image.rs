@@ -0,0 +1,554 @@
+//! Export of grids as images.
+//!
+//! An image is a `Grid<Rgb>`, usually made from the grid of a puzzle with [`Grid::map`] and a
+//! colour mapping. Images are written as binary PPM files, which needs no dependencies, or as PNG
+//! files with the `png` feature. [`Frames`] writes a numbered sequence of images, which can be
+//! turned into a video, e.g. with `ffmpeg -i frames/sand-%05d.png sand.mp4`.
+
+use crate::{day08, day12, day14, locate_parse_error, Grid, Point, Solution};
+use anyhow::{Context, Error, Result};
+use std::{
+    fmt::Display,
+    fs,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// The colour a fraction `t` of the way from this colour to `other`.
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+}
+
+/// Parses colours written as `#rrggbb`, with the `#` being optional.
+impl FromStr for Rgb {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::msg(format!(
+                "invalid colour {s:?}, expected #rrggbb"
+            )));
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        Ok(Rgb(channel(0), channel(2), channel(4)))
+    }
+}
+
+impl Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// A colour gradient through evenly spaced colours, for mapping numbers to colours.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Gradient(Vec<Rgb>);
+
+impl Gradient {
+    pub fn new(colors: Vec<Rgb>) -> Result<Self> {
+        if colors.is_empty() {
+            return Err(Error::msg("a gradient needs at least one colour"));
+        }
+        Ok(Self(colors))
+    }
+
+    /// The colour at `t`, from 0 for the first to 1 for the last colour.
+    pub fn at(&self, t: f64) -> Rgb {
+        let last = self.0.len() - 1;
+        let position = t.clamp(0.0, 1.0) * last as f64;
+        let i = (position.floor() as usize).min(last.saturating_sub(1));
+        match self.0.get(i + 1) {
+            Some(&next) => self.0[i].mix(next, position - i as f64),
+            None => self.0[i],
+        }
+    }
+
+    /// The colour of `value` on a scale from `min` to `max`.
+    pub fn scale(&self, value: f64, min: f64, max: f64) -> Rgb {
+        if max > min {
+            self.at((value - min) / (max - min))
+        } else {
+            self.at(0.0)
+        }
+    }
+}
+
+/// A gradient from dark blue over green to yellow.
+impl Default for Gradient {
+    fn default() -> Self {
+        Self(vec![
+            Rgb(0x44, 0x01, 0x54),
+            Rgb(0x21, 0x91, 0x8c),
+            Rgb(0xfd, 0xe7, 0x25),
+        ])
+    }
+}
+
+/// Parses gradients written as comma-separated colours, e.g. `#000000,#ff8000,#ffffff`.
+impl FromStr for Gradient {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.split(',').map(str::parse).collect::<Result<_>>()?)
+    }
+}
+
+/// Colours each cell by its value, scaled from the smallest to the largest value of the grid.
+pub fn heatmap(values: &Grid<f64>, gradient: &Gradient) -> Grid<Rgb> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values.map(|&v| gradient.scale(v, min, max))
+}
+
+/// Enlarges an image, drawing each pixel as a square of `factor` by `factor` pixels.
+pub fn scale_up(image: &Grid<Rgb>, factor: usize) -> Grid<Rgb> {
+    let factor = factor.max(1);
+    Grid::from_fn(image.width() * factor, image.height() * factor, |p| {
+        image[image.index_unchecked(Point::new(p.x / factor, p.y / factor))]
+    })
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    /// The format given by the extension of `path`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => Ok(ImageFormat::Ppm),
+            Some("png") => Ok(ImageFormat::Png),
+            _ => Err(Error::msg(format!(
+                "unknown image format of {}, expected a .ppm or .png file",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Fails for formats that need a feature which is not enabled.
+    pub fn ensure_supported(self) -> Result<()> {
+        if self == ImageFormat::Png && !cfg!(feature = "png") {
+            return Err(Error::msg("writing PNG images requires the png feature"));
+        }
+        Ok(())
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+fn bytes(image: &Grid<Rgb>) -> Vec<u8> {
+    image.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect()
+}
+
+/// Writes an image in the binary PPM format.
+pub fn write_ppm(image: &Grid<Rgb>, mut out: impl Write) -> Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    out.write_all(&bytes(image))?;
+    Ok(())
+}
+
+/// Writes an image in the PNG format.
+#[cfg(feature = "png")]
+pub fn write_png(image: &Grid<Rgb>, out: impl Write) -> Result<()> {
+    let mut encoder = png::Encoder::new(out, image.width() as u32, image.height() as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&bytes(image))?;
+    writer.finish()?;
+    Ok(())
+}
+
+pub fn write_image(image: &Grid<Rgb>, format: ImageFormat, out: impl Write) -> Result<()> {
+    match format {
+        ImageFormat::Ppm => write_ppm(image, out),
+        #[cfg(feature = "png")]
+        ImageFormat::Png => write_png(image, out),
+        #[cfg(not(feature = "png"))]
+        ImageFormat::Png => format.ensure_supported(),
+    }
+}
+
+/// Saves an image in the format given by the extension of `path`.
+pub fn save(image: &Grid<Rgb>, path: &Path) -> Result<()> {
+    let format = ImageFormat::from_path(path)?;
+    format.ensure_supported()?;
+    let file =
+        fs::File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    write_image(image, format, &mut out)?;
+    out.flush()
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// A numbered sequence of images, saved as `sand-00000.png`, `sand-00001.png` and so on for the
+/// pattern `sand.png`.
+#[derive(Clone, Debug)]
+pub struct Frames {
+    dir: PathBuf,
+    stem: String,
+    format: ImageFormat,
+    count: usize,
+}
+
+impl Frames {
+    /// Starts a sequence named after `pattern`, creating its directory if necessary.
+    pub fn new(pattern: &Path) -> Result<Self> {
+        let format = ImageFormat::from_path(pattern)?;
+        format.ensure_supported()?;
+        let stem = pattern
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .with_context(|| format!("invalid frame name {}", pattern.display()))?
+            .to_owned();
+        let dir = pattern.parent().unwrap_or(Path::new("")).to_owned();
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(&dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        Ok(Self {
+            dir,
+            stem,
+            format,
+            count: 0,
+        })
+    }
+
+    /// Saves the next frame, returning its path.
+    pub fn write(&mut self, image: &Grid<Rgb>) -> Result<PathBuf> {
+        let name = format!(
+            "{}-{:05}.{}",
+            self.stem,
+            self.count,
+            self.format.extension()
+        );
+        let path = self.dir.join(name);
+        save(image, &path)?;
+        self.count += 1;
+        Ok(path)
+    }
+
+    /// The number of frames written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// The colours of the puzzle images, which can be changed by name with [`Palette::set`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Palette {
+    /// The colours of tree heights, scenic scores and hill heights.
+    pub gradient: Gradient,
+    /// The shortest path in the height map.
+    pub path: Rgb,
+    pub rock: Rgb,
+    pub sand: Rgb,
+    pub air: Rgb,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            gradient: Gradient::default(),
+            path: Rgb(0xe4, 0x1a, 0x1c),
+            rock: Rgb(0x60, 0x60, 0x60),
+            sand: Rgb(0xe8, 0xc8, 0x7a),
+            air: Rgb(0x10, 0x10, 0x18),
+        }
+    }
+}
+
+impl Palette {
+    pub const NAMES: [&'static str; 4] = ["path", "rock", "sand", "air"];
+
+    /// Changes one of the colours named in [`Palette::NAMES`].
+    pub fn set(&mut self, name: &str, color: Rgb) -> Result<()> {
+        let field = match name {
+            "path" => &mut self.path,
+            "rock" => &mut self.rock,
+            "sand" => &mut self.sand,
+            "air" => &mut self.air,
+            _ => {
+                return Err(Error::msg(format!(
+                    "unknown colour {name:?}, expected one of {}",
+                    Palette::NAMES.join(", ")
+                )))
+            }
+        };
+        *field = color;
+        Ok(())
+    }
+}
+
+/// Draws the state of a puzzle after solving one of its parts, for the days that have an image:
+///
+/// - day 8: the tree heights, with hidden trees darkened, or the scenic scores on a logarithmic
+///   scale,
+/// - day 12: the heights with the shortest path,
+/// - day 14: the sand at rest in the cave.
+pub fn puzzle_image(day: u8, part: u8, input: &str, palette: &Palette) -> Result<Grid<Rgb>> {
+    let mut image = None;
+    puzzle_frames(day, part, input, palette, usize::MAX, |frame| {
+        image = Some(frame);
+        Ok(())
+    })?;
+    Ok(image.expect("the last frame is always drawn"))
+}
+
+/// Draws the states of a puzzle while solving one of its parts, passing every `every`-th frame
+/// and the final one to `f`. All frames have the same size.
+///
+/// Day 12 has a frame per distance of the search from the end, and day 14 a frame per unit of
+/// sand. Day 8 only has a single frame.
+pub fn puzzle_frames(
+    day: u8,
+    part: u8,
+    input: &str,
+    palette: &Palette,
+    every: usize,
+    mut f: impl FnMut(Grid<Rgb>) -> Result<()>,
+) -> Result<()> {
+    if !(1..=2).contains(&part) {
+        return Err(Error::msg(format!("invalid part {part}")));
+    }
+    let every = every.max(1);
+    match day {
+        8 => f(trees_image(&parse::<day08::Day08>(input)?, part, palette)),
+        12 => search_frames(&parse::<day12::Day12>(input)?, part, palette, every, f),
+        14 => sand_frames(parse::<day14::Day14>(input)?, part, palette, every, f),
+        _ => Err(Error::msg(format!("no image for day {day}"))),
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> Result<S::Parsed> {
+    S::parse(input).map_err(|e| locate_parse_error(input, e))
+}
+
+fn trees_image(trees: &day08::Trees, part: u8, palette: &Palette) -> Grid<Rgb> {
+    if part == 1 {
+        let heights = trees.heights();
+        Grid::from_fn(heights.width(), heights.height(), |p| {
+            let i = heights.index_unchecked(p);
+            let color = palette
+                .gradient
+                .scale(heights[i] as f64, b'0' as f64, b'9' as f64);
+            if trees.visible()[i] {
+                color
+            } else {
+                color.mix(Rgb::BLACK, 0.6)
+            }
+        })
+    } else {
+        let scores = trees.scenic_scores().map(|&s| (s as f64).ln_1p());
+        heatmap(&scores, &palette.gradient)
+    }
+}
+
+fn search_frames(
+    map: &day12::Map,
+    part: u8,
+    palette: &Palette,
+    every: usize,
+    mut f: impl FnMut(Grid<Rgb>) -> Result<()>,
+) -> Result<()> {
+    let search = match part {
+        1 => map.search(day12::Map::is_start),
+        _ => map.search(day12::Map::is_height_a),
+    };
+    let heights = map
+        .heights
+        .map(|&h| palette.gradient.scale(h as f64, b'a' as f64, b'z' as f64));
+    let last = search
+        .goal_distance()
+        .or_else(|| search.distances().values().max().copied())
+        .unwrap_or(0);
+    // The squares reached by the search so far are lightened.
+    let frame = |distance: usize| {
+        Grid::from_fn(heights.width(), heights.height(), |p| {
+            let i = heights.index_unchecked(p);
+            match search.distance(&i) {
+                Some(d) if d <= distance => heights[i].mix(Rgb::WHITE, 0.5),
+                _ => heights[i],
+            }
+        })
+    };
+    for distance in (0..last).step_by(every) {
+        f(frame(distance))?;
+    }
+    let mut image = heights.clone();
+    for i in search.path().unwrap_or_default() {
+        image[i] = palette.path;
+    }
+    f(image)
+}
+
+fn sand_frames(
+    mut map: day14::Map,
+    part: u8,
+    palette: &Palette,
+    every: usize,
+    mut f: impl FnMut(Grid<Rgb>) -> Result<()>,
+) -> Result<()> {
+    map.floor = part == 2;
+    // Run the simulation once to find the area covered by the final frame.
+    let mut last = map.clone();
+    last.trickle();
+    let mut bounds = last.tiles().bounds().context("the cave is empty")?;
+    bounds.extend(500, 0);
+    if map.floor {
+        bounds.extend(bounds.min_x, map.floor_y());
+    }
+    let draw = |map: &day14::Map| {
+        let mut image = map.tiles().to_grid(bounds, |tile| match tile {
+            Some(day14::Tile::Rock) => palette.rock,
+            Some(day14::Tile::Sand) => palette.sand,
+            None => palette.air,
+        });
+        if map.floor {
+            for i in image.row_indices(image.height() - 1) {
+                image[i] = palette.rock;
+            }
+        }
+        image
+    };
+    let mut units = 0;
+    while map.trickle_one() == day14::TrickleResult::Rest {
+        if units % every == 0 {
+            f(draw(&map))?;
+        }
+        units += 1;
+    }
+    f(draw(&map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{example_path, scratch::ScratchDir};
+
+    #[test]
+    fn colors() {
+        assert_eq!("#ff8000".parse::<Rgb>().unwrap(), Rgb(255, 128, 0));
+        assert_eq!("0a0B0c".parse::<Rgb>().unwrap().to_string(), "#0a0b0c");
+        assert!("#ff80".parse::<Rgb>().is_err());
+        assert!("#gg8000".parse::<Rgb>().is_err());
+        assert_eq!(Rgb::BLACK.mix(Rgb(200, 100, 0), 0.5), Rgb(100, 50, 0));
+
+        let gradient: Gradient = "#000000,#ff0000,#ffffff".parse().unwrap();
+        assert_eq!(gradient.at(0.0), Rgb::BLACK);
+        assert_eq!(gradient.at(0.25), Rgb(128, 0, 0));
+        assert_eq!(gradient.at(0.5), Rgb(255, 0, 0));
+        assert_eq!(gradient.at(1.0), Rgb::WHITE);
+        assert_eq!(gradient.at(7.0), Rgb::WHITE);
+        assert_eq!(gradient.scale(15.0, 10.0, 30.0), Rgb(128, 0, 0));
+        assert_eq!(gradient.scale(3.0, 3.0, 3.0), Rgb::BLACK);
+        let single = Gradient::new(vec![Rgb::WHITE]).unwrap();
+        assert_eq!(single.at(0.7), Rgb::WHITE);
+        assert!(Gradient::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn ppm() {
+        let image = Grid::from_vec(vec![Rgb(1, 2, 3), Rgb(4, 5, 6)], 1).unwrap();
+        let mut out = vec![];
+        write_ppm(&scale_up(&image, 2), &mut out).unwrap();
+        let mut expected = b"P6\n2 4\n255\n".to_vec();
+        for pixel in [[1, 2, 3], [4, 5, 6]] {
+            expected.extend(pixel.repeat(4));
+        }
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(
+            ImageFormat::from_path(Path::new("a/b.ppm")).unwrap(),
+            ImageFormat::Ppm
+        );
+        assert_eq!(
+            ImageFormat::from_path(Path::new("b.png")).unwrap(),
+            ImageFormat::Png
+        );
+        assert!(ImageFormat::from_path(Path::new("b.jpg")).is_err());
+        assert!(ImageFormat::from_path(Path::new("b")).is_err());
+        assert_eq!(
+            ImageFormat::Png.ensure_supported().is_ok(),
+            cfg!(feature = "png")
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let dir = ScratchDir::new("frames");
+        let mut frames = Frames::new(&dir.join("out/sand.ppm")).unwrap();
+        let image = Grid::from_vec(vec![Rgb::WHITE], 1).unwrap();
+        frames.write(&image).unwrap();
+        let path = frames.write(&image).unwrap();
+        assert_eq!(path, dir.join("out/sand-00001.ppm"));
+        assert_eq!(frames.count(), 2);
+        assert_eq!(fs::read(path).unwrap(), b"P6\n1 1\n255\n\xff\xff\xff");
+    }
+
+    fn example(day: u8) -> String {
+        fs::read_to_string(example_path(&format!("day{day:02}"), 1)).unwrap()
+    }
+
+    #[test]
+    fn palette() {
+        let mut palette = Palette::default();
+        palette.set("sand", Rgb::WHITE).unwrap();
+        assert_eq!(palette.sand, Rgb::WHITE);
+        assert!(palette.set("water", Rgb::WHITE).is_err());
+    }
+
+    #[test]
+    fn puzzles() {
+        let palette = Palette::default();
+        let count = |image: &Grid<Rgb>, color| image.iter().filter(|&&c| c == color).count();
+
+        let image = puzzle_image(8, 1, &example(8), &palette).unwrap();
+        assert_eq!((image.width(), image.height()), (5, 5));
+        assert_eq!(puzzle_image(8, 2, &example(8), &palette).unwrap().len(), 25);
+
+        let image = puzzle_image(12, 1, &example(12), &palette).unwrap();
+        assert_eq!(count(&image, palette.path), 32);
+
+        let image = puzzle_image(14, 1, &example(14), &palette).unwrap();
+        assert_eq!(count(&image, palette.sand), 24);
+        let mut frames = vec![];
+        puzzle_frames(14, 2, &example(14), &palette, 10, |frame| {
+            frames.push(frame);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(frames.len(), 11);
+        assert_eq!(count(&frames[1], palette.sand), 11);
+        assert_eq!(count(frames.last().unwrap(), palette.sand), 93);
+        assert!(frames.iter().all(|f| f.width() == frames[0].width()));
+
+        assert!(puzzle_image(1, 1, &example(1), &palette).is_err());
+    }
+}
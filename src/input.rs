@@ -0,0 +1,190 @@
+//! Locating and reading puzzle inputs.
+
+use anyhow::{Context, Error, Result};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+/// The environment variable overriding the directory containing the puzzle inputs.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// The directory containing the puzzle inputs.
+///
+/// This is `$AOC_INPUTS_DIR` if set, and the `inputs` directory of this crate otherwise.
+pub fn inputs_dir() -> PathBuf {
+    match std::env::var_os(INPUTS_DIR_VAR) {
+        Some(dir) => dir.into(),
+        None => PathBuf::from_iter([env!("CARGO_MANIFEST_DIR"), "inputs"]),
+    }
+}
+
+pub fn input_path(name: &str) -> PathBuf {
+    inputs_dir().join(name)
+}
+
+pub fn example_path(name: &str, example: u32) -> PathBuf {
+    input_path(&format!("{name}.example{example}"))
+}
+
+/// The example inputs recorded for a day, i.e. `inputs/<name>.example1`, `inputs/<name>.example2`,
+/// and so on.
+pub fn example_paths(name: &str) -> impl Iterator<Item = PathBuf> + '_ {
+    (1..)
+        .map(move |i| example_path(name, i))
+        .take_while(|path| path.exists())
+}
+
+pub fn stdin_isatty() -> bool {
+    unsafe { libc::isatty(libc::STDIN_FILENO) != 0 }
+}
+
+/// Where to read the input of a puzzle from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum InputSource {
+    /// Standard input if it is not a terminal, and the puzzle's input file otherwise.
+    #[default]
+    Auto,
+    /// The puzzle's input file in the inputs directory.
+    File,
+    Stdin,
+    Path(PathBuf),
+    /// One of the example inputs recorded for the puzzle, numbered from 1.
+    Example(u32),
+}
+
+pub const INPUT_OPTIONS_USAGE: &str = "\
+Input options:
+  --input PATH   read the input from PATH, or from standard input if PATH is -
+  -              read the input from standard input
+  --example N    read the N-th recorded example input, inputs/dayNN.exampleN
+
+Inputs are looked up in $AOC_INPUTS_DIR, or in the crate's inputs directory.";
+
+impl InputSource {
+    /// Extracts the input options from command line arguments, returning the selected input
+    /// source and the remaining arguments.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<(Self, Vec<String>)> {
+        let mut source = None;
+        let mut rest = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let selected = match arg.as_str() {
+                "-" => InputSource::Stdin,
+                "--input" => match args.next().context("missing value for --input")? {
+                    path if path == "-" => InputSource::Stdin,
+                    path => InputSource::Path(path.into()),
+                },
+                "--example" => {
+                    let n = args.next().context("missing value for --example")?;
+                    let n = n
+                        .parse()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .with_context(|| format!("invalid example number {n:?}"))?;
+                    InputSource::Example(n)
+                }
+                _ => {
+                    rest.push(arg);
+                    continue;
+                }
+            };
+            if source.replace(selected).is_some() {
+                return Err(Error::msg("more than one input source selected"));
+            }
+        }
+        Ok((source.unwrap_or_default(), rest))
+    }
+
+    /// Whether this source refers to one specific input rather than one input per puzzle.
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, InputSource::Stdin | InputSource::Path(_))
+    }
+
+    /// Reads the input for the puzzle `name`, e.g. `day07`.
+    pub fn read(&self, name: &str) -> Result<String> {
+        match self.path(name) {
+            Some(path) => std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display())),
+            None => read_stdin(),
+        }
+    }
+
+    /// Opens the input for the puzzle `name` to be read a line at a time, without holding all of
+    /// it in memory.
+    pub fn reader(&self, name: &str) -> Result<Box<dyn BufRead>> {
+        match self.path(name) {
+            Some(path) => {
+                let file = File::open(&path)
+                    .with_context(|| format!("failed to open {}", path.display()))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            None => Ok(Box::new(std::io::stdin().lock())),
+        }
+    }
+
+    /// The path of the input for the puzzle `name`, or `None` for standard input.
+    fn path(&self, name: &str) -> Option<PathBuf> {
+        match self {
+            InputSource::Auto if !stdin_isatty() => None,
+            InputSource::Auto | InputSource::File => Some(input_path(name)),
+            InputSource::Stdin => None,
+            InputSource::Path(path) => Some(path.clone()),
+            InputSource::Example(n) => Some(example_path(name, *n)),
+        }
+    }
+}
+
+fn read_stdin() -> Result<String> {
+    std::io::read_to_string(std::io::stdin()).context("failed to read standard input")
+}
+
+/// Reads the input for a day binary, selected by its command line arguments.
+pub fn read_input(bin_name: &str) -> Result<String> {
+    let (source, rest) = InputSource::from_args(std::env::args().skip(1))?;
+    if let Some(arg) = rest.first() {
+        return Err(Error::msg(format!(
+            "unexpected argument {arg:?}\n\nusage: {bin_name} [--input PATH | - | --example N]\n\n\
+             {INPUT_OPTIONS_USAGE}"
+        )));
+    }
+    source.read(bin_name)
+}
+
+#[macro_export]
+macro_rules! read_input {
+    () => {
+        $crate::read_input(env!("CARGO_BIN_NAME"))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_args(args: &[&str]) -> Result<(InputSource, Vec<String>)> {
+        InputSource::from_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn input_options() {
+        let (source, rest) = from_args(&["7", "--example", "2"]).unwrap();
+        assert_eq!(source, InputSource::Example(2));
+        assert_eq!(rest, ["7"]);
+        assert_eq!(from_args(&["-"]).unwrap().0, InputSource::Stdin);
+        assert_eq!(from_args(&["--input", "-"]).unwrap().0, InputSource::Stdin);
+        assert_eq!(
+            from_args(&["--input", "x"]).unwrap().0,
+            InputSource::Path("x".into())
+        );
+        assert_eq!(from_args(&[]).unwrap().0, InputSource::Auto);
+    }
+
+    #[test]
+    fn invalid_input_options() {
+        assert!(from_args(&["--example", "0"]).is_err());
+        assert!(from_args(&["--input"]).is_err());
+        assert!(from_args(&["-", "--example", "1"]).is_err());
+    }
+}
@@ -1,28 +1,183 @@
 use anyhow::{Context, Error, Result};
 use std::{
-    ops::{Deref, DerefMut},
-    path::PathBuf,
+    collections::HashMap,
+    fmt::Display,
+    hash::{BuildHasher, Hash},
     str::FromStr,
 };
 
-pub fn read_input(bin_name: &str) -> std::io::Result<String> {
-    if stdin_isatty() {
-        let path = PathBuf::from_iter([env!("CARGO_MANIFEST_DIR"), "inputs", bin_name]);
-        std::fs::read_to_string(path)
+#[cfg(feature = "visualize")]
+pub mod animation;
+pub mod answers;
+pub mod bench;
+pub mod client;
+mod from_regex;
+pub mod fuzz;
+pub mod generate;
+mod grid;
+pub mod image;
+mod input;
+pub mod ocr;
+pub mod scaffold;
+#[cfg(test)]
+mod scratch;
+pub mod search;
+mod solution;
+mod sparse_grid;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+
+#[doc(hidden)]
+pub use anyhow as __anyhow;
+#[doc(hidden)]
+pub use from_regex::{regex_captures, regex_field};
+pub use grid::{Grid, Point, View, NEIGHBOURS4, NEIGHBOURS8};
+pub use input::{
+    example_path, example_paths, input_path, inputs_dir, read_input, stdin_isatty, InputSource,
+    INPUTS_DIR_VAR, INPUT_OPTIONS_USAGE,
+};
+#[doc(hidden)]
+pub use regex as __regex;
+pub use solution::{DaySelection, Puzzle, Solution};
+pub use sparse_grid::{Bounds, SparseGrid};
+
+pub const PUZZLES: &[Puzzle] = &[
+    Puzzle::new::<day01::Day01>(1),
+    Puzzle::new::<day02::Day02>(2),
+    Puzzle::new::<day03::Day03>(3),
+    Puzzle::new::<day04::Day04>(4),
+    Puzzle::new::<day05::Day05>(5),
+    Puzzle::new::<day06::Day06>(6),
+    Puzzle::new::<day07::Day07>(7),
+    Puzzle::new::<day08::Day08>(8),
+    Puzzle::new::<day09::Day09>(9),
+    Puzzle::new::<day10::Day10>(10),
+    Puzzle::new::<day11::Day11>(11),
+    Puzzle::new::<day12::Day12>(12),
+    Puzzle::new::<day13::Day13>(13),
+    Puzzle::new::<day14::Day14>(14),
+];
+
+pub fn puzzle(day: u8) -> Result<&'static Puzzle> {
+    PUZZLES
+        .iter()
+        .find(|p| p.day() == day)
+        .with_context(|| format!("no solution for day {day}"))
+}
+
+/// An error in a part of the input, carrying the offending text and its position.
+///
+/// The parse helpers below attach a `ParseError` to every error automatically, keeping the most
+/// specific one if the error already carries a position. Since the helpers usually only see a
+/// part of the input, the line and column are filled in later by [`locate_parse_error`], which
+/// `Puzzle::parse` and `Solution::solve` do for the complete input.
+#[derive(Debug)]
+pub struct ParseError {
+    text: String,
+    addr: usize,
+    position: Option<(usize, usize)>,
+    error: Error,
+}
+
+impl ParseError {
+    pub fn new(text: &str, error: Error) -> Self {
+        Self {
+            text: text.to_owned(),
+            addr: text.as_ptr() as usize,
+            position: None,
+            error,
+        }
+    }
+
+    /// The offending text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The line and column of the offending text, both starting at 1, if known.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.position
+    }
+
+    /// Determines the position of the offending text if it is a part of `input`.
+    fn locate(&mut self, input: &str) {
+        let Some(offset) = self.addr.checked_sub(input.as_ptr() as usize) else {
+            return;
+        };
+        let Some(before) = input.get(..offset) else {
+            return;
+        };
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        self.position = Some((line, column));
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#}", self.error)?;
+        if let Some((line, column)) = self.position {
+            write!(f, " at line {line}, column {column}")?;
+        }
+        match self.text.split_once('\n') {
+            Some((first_line, _)) => write!(f, ": {first_line:?}..."),
+            None => write!(f, ": {:?}", self.text),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Attaches the position of `text` to `error`, unless it already carries one.
+pub fn with_span(text: &str, error: Error) -> Error {
+    if error.downcast_ref::<ParseError>().is_some() {
+        error
     } else {
-        std::io::read_to_string(&mut std::io::stdin())
+        ParseError::new(text, error).into()
     }
 }
 
-#[macro_export]
-macro_rules! read_input {
-    () => {
-        $crate::read_input(env!("CARGO_BIN_NAME"))
-    };
+/// Fills in the line and column of a `ParseError` in `error` relative to the complete `input`.
+pub fn locate_parse_error(input: &str, mut error: Error) -> Error {
+    if let Some(parse_error) = error.downcast_mut::<ParseError>() {
+        parse_error.locate(input);
+    }
+    error
+}
+
+/// Parses `s`, attaching its position to any error.
+pub fn parse_at<T>(s: &str) -> Result<T>
+where
+    T: FromStr,
+    Error: From<<T as FromStr>::Err>,
+{
+    s.parse().map_err(|e| with_span(s, Error::from(e)))
 }
 
-pub fn stdin_isatty() -> bool {
-    unsafe { libc::isatty(libc::STDIN_FILENO) != 0 }
+/// Applies `f` to each non-empty line of `block`, attaching the position of the line to errors.
+pub fn parse_lines_with<'a, T, F>(block: &'a str, mut f: F) -> impl Iterator<Item = Result<T>> + 'a
+where
+    T: 'a,
+    F: FnMut(&'a str) -> Result<T> + 'a,
+{
+    block
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(move |line| f(line).map_err(|e| with_span(line, e)))
 }
 
 pub fn parse_split<'a, T>(input: &'a str, sep: char) -> impl Iterator<Item = Result<T>> + 'a
@@ -30,7 +185,7 @@ where
     T: FromStr + 'a,
     Error: From<<T as FromStr>::Err>,
 {
-    input.trim().split(sep).map(|s| Ok(s.trim().parse()?))
+    input.trim().split(sep).map(|s| parse_at(s.trim()))
 }
 
 pub fn parse_split_comma<'a, T>(line: &'a str) -> impl Iterator<Item = Result<T>> + 'a
@@ -57,98 +212,250 @@ where
     parse_split(block, '\n')
 }
 
-#[derive(Clone, Debug)]
-pub struct Grid<T> {
-    data: Vec<T>,
-    width: usize,
-    height: usize,
+/// A block of consecutive non-blank lines, as separated by blank lines in many inputs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Block<'a> {
+    /// The lines of the block, without the line break after the last one.
+    pub text: &'a str,
+    /// The line number of the first line of the block, starting at 1.
+    pub line: usize,
 }
 
-impl<T> Deref for Grid<T> {
-    type Target = [T];
+/// The role of a line in the blocks of an input, see [`BlockLines`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockLine {
+    /// The first line of a block.
+    First,
+    /// A line continuing the current block.
+    Next,
+    /// A blank line, which ends the current block if there is one.
+    Blank,
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.data
-    }
+/// Splits lines into blocks one line at a time, for [`blocks`] as well as for readers that never
+/// hold the whole input. Lines containing only whitespace count as blank.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlockLines {
+    in_block: bool,
 }
 
-impl<T> DerefMut for Grid<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.data
+impl BlockLines {
+    /// Classifies the next line, which may still end with `\n` or `\r\n`.
+    pub fn classify(&mut self, line: &str) -> BlockLine {
+        if line.trim().is_empty() {
+            self.in_block = false;
+            BlockLine::Blank
+        } else if std::mem::replace(&mut self.in_block, true) {
+            BlockLine::Next
+        } else {
+            BlockLine::First
+        }
     }
 }
 
-impl<T: Default> Grid<T> {
-    pub fn new(width: usize, height: usize) -> Self {
-        let mut data = vec![];
-        data.resize_with(width * height, T::default);
-        Self {
-            data,
-            width,
-            height,
+/// Splits `input` into blocks separated by one or more blank lines. Lines may end with `\n` or
+/// `\r\n`, and lines containing only whitespace count as blank.
+pub fn blocks(input: &str) -> impl Iterator<Item = Block<'_>> {
+    let mut block_lines = BlockLines::default();
+    let mut lines = input.split_inclusive('\n').enumerate();
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        // The line number, start and end of the current block.
+        let mut block: Option<(usize, usize, usize)> = None;
+        for (i, line) in lines.by_ref() {
+            let start = offset;
+            offset += line.len();
+            let end = start + line.trim_end_matches(['\r', '\n']).len();
+            match (block_lines.classify(line), &mut block) {
+                (BlockLine::First, _) => block = Some((i + 1, start, end)),
+                (BlockLine::Next, Some((_, _, block_end))) => *block_end = end,
+                (BlockLine::Blank, Some(_)) => break,
+                _ => {}
+            }
         }
-    }
+        block.map(|(line, start, end)| Block {
+            text: &input[start..end],
+            line,
+        })
+    })
+}
+
+/// Applies `f` to each block of `input`, attaching the position of the block to errors.
+pub fn parse_blocks_with<'a, T, F>(input: &'a str, mut f: F) -> impl Iterator<Item = Result<T>> + 'a
+where
+    T: 'a,
+    F: FnMut(Block<'a>) -> Result<T> + 'a,
+{
+    blocks(input).map(move |block| f(block).map_err(|e| with_span(block.text, e)))
+}
+
+pub fn parse_blocks<'a, T>(input: &'a str) -> impl Iterator<Item = Result<T>> + 'a
+where
+    T: FromStr + 'a,
+    Error: From<<T as FromStr>::Err>,
+{
+    blocks(input).map(|block| parse_at(block.text))
 }
 
-impl<T> Grid<T> {
-    pub fn width(&self) -> usize {
-        self.width
+/// Splits `input` into exactly `N` blocks, e.g. a header and a body with different layouts.
+pub fn split_blocks<const N: usize>(input: &str) -> Result<[Block<'_>; N]> {
+    let blocks: Vec<Block> = blocks(input).collect();
+    let count = blocks.len();
+    blocks.try_into().map_err(|_| {
+        Error::msg(format!(
+            "expected {N} blocks separated by blank lines, found {count}"
+        ))
+    })
+}
+
+/// Mutable references to the values of two different keys, or `None` if the keys are equal or
+/// either of them is missing.
+pub fn get_pair_mut<'a, K, V, S>(
+    map: &'a mut HashMap<K, V, S>,
+    a: &K,
+    b: &K,
+) -> Option<(&'a mut V, &'a mut V)>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    if a == b {
+        return None;
+    }
+    match map.get_disjoint_mut([a, b]) {
+        [Some(a), Some(b)] => Some((a, b)),
+        _ => None,
     }
+}
+
+/// The non-overlapping chunks of `N` elements of a slice, ignoring a shorter remainder.
+pub fn array_chunks<T, const N: usize>(slice: &[T]) -> impl Iterator<Item = &[T; N]> + '_ {
+    slice.chunks_exact(N).map(|chunk| chunk.try_into().unwrap())
+}
 
-    pub fn height(&self) -> usize {
-        self.height
+/// The overlapping windows of `N` consecutive elements of a slice.
+pub fn array_windows<T, const N: usize>(slice: &[T]) -> impl Iterator<Item = &[T; N]> + '_ {
+    slice.windows(N).map(|window| window.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_position() {
+        let input = "1,2\n3,x4\n";
+        let error = input
+            .lines()
+            .flat_map(parse_split_comma::<u32>)
+            .find_map(Result::err)
+            .unwrap();
+        let error = locate_parse_error(input, error);
+        let parse_error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(parse_error.position(), Some((2, 3)));
+        assert_eq!(parse_error.text(), "x4");
+        assert_eq!(
+            error.to_string(),
+            "invalid digit found in string at line 2, column 3: \"x4\""
+        );
     }
 
-    pub fn from_lines<'a, I, F, L>(lines: I, mut line_parser: F) -> Result<Self>
-    where
-        I: IntoIterator<Item = &'a str>,
-        F: FnMut(&'a str) -> L,
-        L: Iterator<Item = Result<T>> + 'a,
-    {
-        let mut data = Vec::new();
-        let mut width = None;
-        for line in lines {
-            let start_index = data.len();
-            for item in line_parser(line) {
-                data.push(item?);
-            }
-            let line_len = data.len() - start_index;
-            if *width.get_or_insert(line_len) != line_len {
-                return Err(Error::msg("all lines must have the same length"));
-            }
-        }
-        let width = width.context("no data in grid")?;
-        let height = data.len() / width;
-        Ok(Self {
-            data,
-            width,
-            height,
+    #[test]
+    fn innermost_span_wins() {
+        // The span of the word is kept under the context of the line, instead of the span of
+        // the whole line that parse_lines_with would attach.
+        let input = "a\nb c";
+        let error = parse_lines_with(input, |line| match line.split_once(' ') {
+            Some((_, word)) => Err(with_span(word, Error::msg("bad word"))).context("bad line"),
+            None => Ok(()),
         })
+        .find_map(Result::err)
+        .unwrap();
+        let error = locate_parse_error(input, error);
+        let parse_error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(parse_error.text(), "c");
+        assert_eq!(parse_error.position(), Some((2, 3)));
+        assert_eq!(
+            format!("{error:#}"),
+            "bad line: bad word at line 2, column 3: \"c\""
+        );
     }
 
-    pub fn index(&self, x: usize, y: usize) -> Option<usize> {
-        (x >= self.width || y >= self.height).then_some(self.width * x + y)
+    #[test]
+    fn pair_mut() {
+        let mut map = HashMap::from([(1, vec![1]), (2, vec![2])]);
+        let (a, b) = get_pair_mut(&mut map, &1, &2).unwrap();
+        b.append(a);
+        assert!(map[&1].is_empty());
+        assert_eq!(map[&2], [2, 1]);
+        assert!(get_pair_mut(&mut map, &1, &1).is_none());
+        assert!(get_pair_mut(&mut map, &1, &3).is_none());
     }
 
-    pub fn coords(&self, index: usize) -> (usize, usize) {
-        (index % self.width, index / self.width)
+    #[test]
+    fn array_chunks_and_windows() {
+        let v = [1, 2, 3, 4, 5];
+        let chunks: Vec<&[i32; 2]> = array_chunks(&v).collect();
+        assert_eq!(chunks, [&[1, 2], &[3, 4]]);
+        let windows: Vec<[i32; 3]> = array_windows(&v).copied().collect();
+        assert_eq!(windows, [[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+        assert_eq!(array_windows::<_, 6>(&v).count(), 0);
     }
 
-    pub fn get_xy(&self, x: usize, y: usize) -> Option<&T> {
-        self.index(x, y).map(|i| &self.data[i])
-    }
+    #[test]
+    fn blocks_with_line_numbers() {
+        let input = "\n1\n2\n\n\n  \n3\r\n\r\n4\r\n5 \r\n\n";
+        let blocks: Vec<(&str, usize)> = blocks(input).map(|b| (b.text, b.line)).collect();
+        assert_eq!(blocks, [("1\n2", 2), ("3", 7), ("4\r\n5 ", 9)]);
+        assert_eq!(super::blocks("").count(), 0);
+        assert_eq!(super::blocks("\n \n").count(), 0);
+        assert_eq!(super::blocks("x").next().unwrap().text, "x");
 
-    pub fn get_xy_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
-        self.index(x, y).map(|i| &mut self.data[i])
+        let mut block_lines = BlockLines::default();
+        let kinds: Vec<BlockLine> = input
+            .split_inclusive('\n')
+            .map(|line| block_lines.classify(line))
+            .collect();
+        use BlockLine::*;
+        assert_eq!(
+            kinds,
+            [Blank, First, Next, Blank, Blank, Blank, First, Blank, First, Next, Blank]
+        );
     }
 
-    pub fn neighbours(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
-        (0..4).filter_map(move |j| match j {
-            0 => i.checked_sub(self.width),
-            1 if i % self.width > 0 => Some(i - 1),
-            2 if (i + 1) % self.width > 0 => Some(i + 1),
-            3 if i + self.width < self.data.len() => Some(i + self.width),
-            _ => None,
+    #[test]
+    fn parse_blocks_error_position() {
+        let input = "1\n2\n\n3\nx\n";
+        let sums: Vec<u32> =
+            parse_blocks_with("1\n2\n\n3\n", |block| parse_lines::<u32>(block.text).sum())
+                .collect::<Result<_>>()
+                .unwrap();
+        assert_eq!(sums, [3, 3]);
+        let error = parse_blocks_with(input, |block| {
+            parse_lines::<u32>(block.text).sum::<Result<u32>>()
         })
+        .find_map(Result::err)
+        .unwrap();
+        let error = locate_parse_error(input, error);
+        let parse_error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(parse_error.position(), Some((5, 1)));
+        let error = parse_blocks::<u32>(input).nth(1).unwrap().unwrap_err();
+        let error = locate_parse_error(input, error);
+        let parse_error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(
+            (parse_error.position(), parse_error.text()),
+            (Some((4, 1)), "3\nx")
+        );
+    }
+
+    #[test]
+    fn header_and_body() {
+        let [header, body] = split_blocks("a\n\nb\nc\n").unwrap();
+        assert_eq!((header.text, body.text, body.line), ("a", "b\nc", 3));
+        let error = split_blocks::<2>("a\n\nb\n\nc").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected 2 blocks separated by blank lines, found 3"
+        );
     }
 }
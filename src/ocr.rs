@@ -0,0 +1,179 @@
+//! Recognition of the letters drawn by some puzzles, such as the CRT image of day 10.
+//!
+//! Letters are four pixels wide and six pixels high, and are separated by a blank column. Dark
+//! pixels are drawn as `.` or a space, and every other character is a lit pixel.
+
+use anyhow::{Context, Error, Result};
+
+/// The height of a letter in pixels.
+pub const GLYPH_HEIGHT: usize = 6;
+/// The width of a letter in pixels, without the blank column that follows it.
+pub const GLYPH_WIDTH: usize = 4;
+
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the letters of an image given as six lines of pixels.
+pub fn decode(image: &str) -> Result<String> {
+    let rows: Vec<Vec<bool>> = image
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+    decode_rows(&rows)
+}
+
+/// Reads the letters of an image given as pixels in row-major order, with `width` pixels per row.
+pub fn decode_pixels(pixels: &[char], width: usize) -> Result<String> {
+    if width == 0 || !pixels.len().is_multiple_of(width) {
+        return Err(Error::msg(format!(
+            "{} pixels cannot be split into rows of {width}",
+            pixels.len()
+        )));
+    }
+    let rows: Vec<Vec<bool>> = pixels
+        .chunks_exact(width)
+        .map(|row| row.iter().copied().map(is_lit).collect())
+        .collect();
+    decode_rows(&rows)
+}
+
+/// Draws `text` in the font recognized by [`decode`], with `#` for lit and `.` for dark pixels.
+///
+/// Every letter is followed by a blank column, and the rows are separated by newlines.
+pub fn render(text: &str) -> Result<String> {
+    let glyphs = text
+        .chars()
+        .map(|c| {
+            FONT.iter()
+                .find(|&&(letter, _)| letter == c)
+                .map(|(_, glyph)| glyph)
+                .with_context(|| format!("no glyph for {c:?}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let rows: Vec<String> = (0..GLYPH_HEIGHT)
+        .map(|y| {
+            glyphs
+                .iter()
+                .map(|glyph| format!("{}.", glyph[y]))
+                .collect()
+        })
+        .collect();
+    Ok(rows.join("\n"))
+}
+
+fn decode_rows(rows: &[Vec<bool>]) -> Result<String> {
+    if rows.len() != GLYPH_HEIGHT {
+        return Err(Error::msg(format!(
+            "expected {GLYPH_HEIGHT} rows of pixels, found {}",
+            rows.len()
+        )));
+    }
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let pixel = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let mut text = String::new();
+    for left in (0..width).step_by(GLYPH_WIDTH + 1) {
+        // The glyph together with the blank column following it.
+        let cell: [String; GLYPH_HEIGHT] = std::array::from_fn(|y| {
+            (left..=left + GLYPH_WIDTH)
+                .map(|x| if pixel(x, y) { '#' } else { '.' })
+                .collect()
+        });
+        let letter = FONT.iter().find(|(_, glyph)| {
+            glyph
+                .iter()
+                .zip(&cell)
+                .all(|(glyph_row, cell_row)| cell_row == &format!("{glyph_row}."))
+        });
+        match letter {
+            Some(&(letter, _)) => text.push(letter),
+            None => {
+                return Err(Error::msg(format!(
+                    "unknown glyph at columns {left}..={} after {text:?}:\n{}",
+                    left + GLYPH_WIDTH,
+                    cell.join("\n")
+                )))
+            }
+        }
+    }
+    Ok(text)
+}
+
+fn is_lit(pixel: char) -> bool {
+    !matches!(pixel, '.' | ' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_letters() {
+        let image = [
+            "###..####.#..#.",
+            "#..#.#....#..#.",
+            "#..#.###..####.",
+            "###..#....#..#.",
+            "#.#..#....#..#.",
+            "#..#.####.#..#.",
+        ]
+        .join("\n");
+        assert_eq!(decode(&image).unwrap(), "REH");
+        let image = image.replace('#', "█").replace('.', " ");
+        assert_eq!(decode(&image).unwrap(), "REH");
+
+        // Trailing dark pixels may be missing.
+        let trimmed: Vec<&str> = image.lines().map(str::trim_end).collect();
+        assert_eq!(decode(&trimmed.join("\n")).unwrap(), "REH");
+
+        let pixels: Vec<char> = image.lines().flat_map(str::chars).collect();
+        assert_eq!(decode_pixels(&pixels, 15).unwrap(), "REH");
+        assert!(decode_pixels(&pixels, 16).is_err());
+    }
+
+    #[test]
+    fn render_round_trip() {
+        let letters: String = FONT.iter().map(|&(letter, _)| letter).collect();
+        let image = render(&letters).unwrap();
+        assert_eq!(image.lines().count(), GLYPH_HEIGHT);
+        assert_eq!(image.lines().next().unwrap().len(), 5 * FONT.len());
+        assert_eq!(decode(&image).unwrap(), letters);
+        assert!(render("AbC").is_err());
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let mut image: Vec<String> = render("AB").unwrap().lines().map(str::to_owned).collect();
+        image[2].replace_range(8..9, "#");
+        assert_eq!(
+            decode(&image.join("\n")).unwrap_err().to_string(),
+            "unknown glyph at columns 5..=9 after \"A\":\n\
+             ###..\n\
+             #..#.\n\
+             ####.\n\
+             #..#.\n\
+             #..#.\n\
+             ###.."
+        );
+        assert_eq!(
+            decode("#\n#").unwrap_err().to_string(),
+            "expected 6 rows of pixels, found 2"
+        );
+    }
+}
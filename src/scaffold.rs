@@ -0,0 +1,287 @@
+//! Scaffolding for the solution of a new day, as created by `aoc new`.
+//!
+//! A new day consists of the library module `src/dayNN.rs`, the binary `src/bin/dayNN.rs`, a
+//! placeholder example input with an empty answers file, and entries in the module list and the
+//! `PUZZLES` registry in `src/lib.rs` and in the answer tests in `tests/answers.rs`. Nothing is
+//! downloaded, and no existing file is overwritten.
+
+use anyhow::{Context, Error, Result};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The year of the puzzles solved by this crate, taken from its name.
+pub fn default_year() -> u16 {
+    env!("CARGO_PKG_NAME")
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .parse()
+        .expect("crate name should end with the year")
+}
+
+/// The day of the month of the latest puzzle, i.e. today in US Eastern Standard Time, at
+/// midnight of which the puzzles are released.
+pub fn current_day() -> u8 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    puzzle_day(now)
+}
+
+/// The day of the month in US Eastern Standard Time at `secs` seconds after the Unix epoch.
+fn puzzle_day(secs: i64) -> u8 {
+    const EST_OFFSET: i64 = -5 * 60 * 60;
+    day_of_month((secs + EST_OFFSET).div_euclid(24 * 60 * 60))
+}
+
+/// The day of the month of the date `days` days after 1970-01-01.
+fn day_of_month(days: i64) -> u8 {
+    // Counts days from 0000-03-01 in eras of 400 years, so that leap days end each year.
+    let days = days + 719_468;
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months from March have 153 days in every five.
+    let month = (5 * day_of_year + 2) / 153;
+    (day_of_year - (153 * month + 2) / 5 + 1) as u8
+}
+
+/// Creates the files for a new day in the crate at `root` and registers the day, returning the
+/// paths of all created and modified files.
+pub fn new_day(root: &Path, day: u8, year: u16) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::msg(format!("invalid day {day}, must be 1 to 25")));
+    }
+    let name = format!("day{day:02}");
+    let files = [
+        (format!("src/{name}.rs"), module_template(day, year)),
+        (format!("src/bin/{name}.rs"), bin_template(day)),
+        (format!("inputs/{name}.example1"), String::new()),
+        (format!("inputs/{name}.example1.answers"), String::new()),
+    ];
+    for (path, _) in &files {
+        if root.join(path).exists() {
+            return Err(Error::msg(format!("{path} already exists")));
+        }
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let mut lib = read(&lib_path)?;
+    lib = insert_sorted(&lib, "pub mod day", &format!("pub mod {name};"))?;
+    lib = insert_sorted(
+        &lib,
+        "    Puzzle::new::<day",
+        &format!("    Puzzle::new::<{name}::Day{day:02}>({day}),"),
+    )?;
+    let tests_path = root.join("tests/answers.rs");
+    let tests = insert_sorted(
+        &read(&tests_path)?,
+        "    day",
+        &format!("    {name} => {day},"),
+    )?;
+
+    // All checks passed, so the files can be written without leaving a half-finished day behind.
+    let mut written = vec![];
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        written.push(path);
+    }
+    for (path, contents) in [(lib_path, lib), (tests_path, tests)] {
+        fs::write(&path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))
+}
+
+/// Inserts `line` among the lines starting with `prefix`, which are assumed to be sorted.
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.contains(&line) {
+        return Err(Error::msg(format!(
+            "{:?} is already registered",
+            line.trim()
+        )));
+    }
+    let matching: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with(prefix))
+        .collect();
+    let last = *matching
+        .last()
+        .with_context(|| format!("no line starting with {prefix:?} found"))?;
+    let index = matching
+        .into_iter()
+        .find(|&i| lines[i] > line)
+        .unwrap_or(last + 1);
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn module_template(day: u8, year: u16) -> String {
+    format!(
+        "\
+//! Day {day}
+//!
+//! <https://adventofcode.com/{year}/day/{day}>
+
+use crate::Solution;
+use anyhow::{{Error, Result}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Parsed = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed> {{
+        Ok(input.lines().map(str::to_owned).collect())
+    }}
+
+    fn part1(_lines: &Self::Parsed) -> Result<u64> {{
+        Err(Error::msg(\"part 1 is not solved yet\"))
+    }}
+
+    fn part2(_lines: &Self::Parsed) -> Result<u64> {{
+        Err(Error::msg(\"part 2 is not solved yet\"))
+    }}
+}}
+"
+    )
+}
+
+fn bin_template(day: u8) -> String {
+    format!(
+        "\
+use anyhow::Result;
+use {crate_name}::{{day{day:02}::Day{day:02}, read_input, Solution}};
+
+fn main() -> Result<()> {{
+    let (part1, part2) = Day{day:02}::solve(&read_input!()?)?;
+    println!(\"{{part1}}\");
+    println!(\"{{part2}}\");
+    Ok(())
+}}
+",
+        crate_name = env!("CARGO_CRATE_NAME"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scratch::ScratchDir;
+
+    /// A scratch copy of the parts of this crate that `new_day` touches.
+    struct Crate(ScratchDir);
+
+    impl Crate {
+        fn new(test: &str) -> Self {
+            let root = ScratchDir::new(&format!("scaffold-{test}"));
+            for dir in ["src/bin", "tests", "inputs"] {
+                fs::create_dir_all(root.join(dir)).unwrap();
+            }
+            fs::write(root.join("src/lib.rs"), include_str!("lib.rs")).unwrap();
+            fs::write(
+                root.join("tests/answers.rs"),
+                include_str!("../tests/answers.rs"),
+            )
+            .unwrap();
+            Self(root)
+        }
+
+        fn read(&self, path: &str) -> String {
+            fs::read_to_string(self.0.join(path)).unwrap()
+        }
+    }
+
+    #[test]
+    fn new_day_registers_day() {
+        let root = Crate::new("register");
+        let written = new_day(&root.0, 25, 2022).unwrap();
+        assert_eq!(written.len(), 6);
+        assert!(root.read("src/day25.rs").contains("pub struct Day25;"));
+        assert!(root.read("src/day25.rs").contains("/2022/day/25>"));
+        assert!(root.read("src/bin/day25.rs").contains("Day25::solve"));
+        assert_eq!(root.read("inputs/day25.example1.answers"), "");
+        // The answer test skips the real input only while it has no answers file.
+        assert!(!root.0.join("inputs/day25").exists());
+        assert!(!root.0.join("inputs/day25.answers").exists());
+        let lib = root.read("src/lib.rs");
+        assert!(lib.contains("pub mod day14;\npub mod day25;\n"));
+        assert!(lib.contains("(14),\n    Puzzle::new::<day25::Day25>(25),\n];"));
+        let tests = root.read("tests/answers.rs");
+        assert!(tests.contains("    day14 => 14,\n    day25 => 25,\n}"));
+    }
+
+    #[test]
+    fn insert_keeps_lines_sorted() {
+        let text = "a\nday01\nday04\nb\n";
+        assert_eq!(
+            insert_sorted(text, "day", "day03").unwrap(),
+            "a\nday01\nday03\nday04\nb\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "day", "day05").unwrap(),
+            "a\nday01\nday04\nday05\nb\n"
+        );
+        assert!(insert_sorted(text, "day", "day04").is_err());
+        assert!(insert_sorted(text, "mod", "mod x").is_err());
+    }
+
+    #[test]
+    fn new_day_refuses_to_overwrite() {
+        let root = Crate::new("overwrite");
+        fs::write(root.0.join("src/bin/day15.rs"), "existing").unwrap();
+        let lib = root.read("src/lib.rs");
+        let err = new_day(&root.0, 15, 2022).unwrap_err();
+        assert_eq!(err.to_string(), "src/bin/day15.rs already exists");
+        assert_eq!(root.read("src/bin/day15.rs"), "existing");
+        assert_eq!(root.read("src/lib.rs"), lib);
+        assert!(!root.0.join("src/day15.rs").exists());
+
+        // Days that are already registered are rejected as well.
+        let err = new_day(&root.0, 14, 2022).unwrap_err();
+        assert_eq!(err.to_string(), "\"pub mod day14;\" is already registered");
+        assert!(new_day(&root.0, 26, 2022).is_err());
+        assert!(new_day(&root.0, 0, 2022).is_err());
+    }
+
+    #[test]
+    fn year_from_crate_name() {
+        assert_eq!(default_year(), 2022);
+    }
+
+    #[test]
+    fn days_of_month() {
+        assert_eq!(day_of_month(0), 1);
+        assert_eq!(day_of_month(-1), 31);
+        assert_eq!(day_of_month(19_351), 25);
+        assert_eq!(day_of_month(11_016), 29);
+        assert!((1..=31).contains(&current_day()));
+    }
+
+    #[test]
+    fn puzzles_released_at_midnight_est() {
+        // 2022-12-25 05:00 UTC is midnight in EST.
+        let release = 1_671_944_400;
+        assert_eq!(puzzle_day(release - 1), 24);
+        assert_eq!(puzzle_day(release), 25);
+        assert_eq!(puzzle_day(release + 24 * 3600 - 1), 25);
+        // 01:00 UTC on the 25th is still the 24th in EST.
+        assert_eq!(puzzle_day(release - 4 * 3600), 24);
+    }
+}
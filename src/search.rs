@@ -0,0 +1,328 @@
+//! Shortest path searches over implicit graphs.
+//!
+//! The graph is given by a function returning the neighbours of a node, so grids and other
+//! puzzle structures can be searched without building an explicit graph first. All searches
+//! accept multiple sources and stop early at the first node matching a goal predicate; pass
+//! `|_| false` to explore everything reachable.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// The result of a search: the distances of all settled nodes and how they were reached.
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    dist: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    /// The shortest distance from any source to `node`, if it was reached before the search
+    /// stopped.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.dist
+    }
+
+    /// The node matching the goal predicate at which the search stopped, if any.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goal()?)
+    }
+
+    /// The nodes on a shortest path from one of the sources to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The nodes on a shortest path from one of the sources to the goal, both included.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal()?)
+    }
+}
+
+/// Breadth-first search, where every edge has a length of one.
+pub fn bfs<N, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut dist = HashMap::new();
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if !dist.contains_key(&source) {
+            dist.insert(source.clone(), 0);
+            queue.push_back(source);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return Search {
+                dist,
+                parents,
+                goal: Some(node),
+            };
+        }
+        let next_dist = dist[&node] + 1;
+        for next in neighbours(&node) {
+            if !dist.contains_key(&next) {
+                dist.insert(next.clone(), next_dist);
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    Search {
+        dist,
+        parents,
+        goal: None,
+    }
+}
+
+/// Dijkstra's algorithm, with the length of each edge given by `cost(from, to)`.
+pub fn dijkstra<N, C, I>(
+    sources: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    cost: impl FnMut(&N, &N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = N>,
+{
+    astar(sources, neighbours, cost, |_| C::default(), goal)
+}
+
+/// A* search, guided by a `heuristic` estimating the remaining distance to the goal.
+///
+/// The heuristic must never overestimate the distance and must be consistent, i.e. it must not
+/// decrease by more than the cost of any edge, otherwise the distances may not be the shortest.
+pub fn astar<N, C, I>(
+    sources: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut cost: impl FnMut(&N, &N) -> C,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = N>,
+{
+    // The best distances found so far, which only become final once a node is taken off the
+    // queue and moved to `dist`.
+    let mut best = HashMap::new();
+    let mut parents = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for source in sources {
+        if best.insert(source.clone(), C::default()).is_none() {
+            queue.push(QueueEntry {
+                priority: heuristic(&source),
+                cost: C::default(),
+                node: source,
+            });
+        }
+    }
+    let mut dist = HashMap::new();
+    while let Some(QueueEntry {
+        cost: current,
+        node,
+        ..
+    }) = queue.pop()
+    {
+        if dist.contains_key(&node) {
+            continue;
+        }
+        dist.insert(node.clone(), current);
+        if goal(&node) {
+            return Search {
+                dist,
+                parents,
+                goal: Some(node),
+            };
+        }
+        for next in neighbours(&node) {
+            if dist.contains_key(&next) {
+                continue;
+            }
+            let next_cost = current + cost(&node, &next);
+            if best.get(&next).is_none_or(|&c| next_cost < c) {
+                best.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                queue.push(QueueEntry {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    Search {
+        dist,
+        parents,
+        goal: None,
+    }
+}
+
+/// An entry of the priority queue, ordered so that the lowest priority is popped first.
+struct QueueEntry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for QueueEntry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for QueueEntry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for QueueEntry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for QueueEntry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Point};
+
+    /// A small weighted graph, where the direct edge from 0 to 3 is longer than the detour.
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    fn weighted(sources: &[u32], target: u32) -> Search<u32, u32> {
+        dijkstra(
+            sources.iter().copied(),
+            |n| edges(n).into_iter().map(|(m, _)| m),
+            |a, b| edges(a).into_iter().find(|&(m, _)| m == *b).unwrap().1,
+            |&n| n == target,
+        )
+    }
+
+    /// A maze with walls marked as `#`.
+    fn maze() -> Grid<u8> {
+        let lines = ["..#....", ".##.##.", "....#..", ".#.##.#", "...#..."];
+        Grid::from_lines(lines, |line| line.bytes().map(Ok)).unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<u8>, p: Point) -> Vec<Point> {
+        grid.neighbours4(p)
+            .filter(|&q| grid.at(q) == Some(&b'.'))
+            .collect()
+    }
+
+    #[test]
+    fn bfs_distances_and_path() {
+        let grid = maze();
+        let end = Point::new(6, 4);
+        let search = bfs(
+            [Point::new(0, 0)],
+            |&p| open_neighbours(&grid, p),
+            |&p| p == end,
+        );
+        assert_eq!(search.goal(), Some(&end));
+        assert_eq!(search.goal_distance(), Some(16));
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 17);
+        assert_eq!(path[0], Point::new(0, 0));
+        assert_eq!(path[16], end);
+        for step in path.windows(2) {
+            assert!(open_neighbours(&grid, step[0]).contains(&step[1]));
+        }
+    }
+
+    #[test]
+    fn bfs_without_goal_explores_everything() {
+        let grid = maze();
+        let search = bfs(
+            [Point::new(0, 0)],
+            |&p| open_neighbours(&grid, p),
+            |_| false,
+        );
+        assert_eq!(search.goal(), None);
+        assert_eq!(search.path(), None);
+        let open = grid.iter().filter(|&&c| c == b'.').count();
+        assert_eq!(search.distances().len(), open);
+        assert_eq!(search.distance(&Point::new(2, 0)), None);
+    }
+
+    #[test]
+    fn multiple_sources() {
+        let grid = maze();
+        let sources = [Point::new(0, 0), Point::new(6, 0)];
+        let search = bfs(sources, |&p| open_neighbours(&grid, p), |_| false);
+        assert_eq!(search.distance(&Point::new(6, 4)), Some(6));
+        assert_eq!(
+            search.path_to(&Point::new(6, 2)).unwrap()[0],
+            Point::new(6, 0)
+        );
+        assert_eq!(weighted(&[0, 2], 3).goal_distance(), Some(3));
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheaper_detours() {
+        let search = weighted(&[0], 3);
+        assert_eq!(search.goal_distance(), Some(6));
+        assert_eq!(search.path(), Some(vec![0, 1, 2, 3]));
+        assert_eq!(weighted(&[0], 4).goal(), None);
+        assert_eq!(weighted(&[3], 3).path(), Some(vec![3]));
+    }
+
+    #[test]
+    fn astar_agrees_with_bfs() {
+        let grid = maze();
+        let start = Point::new(0, 0);
+        let reachable = bfs([start], |&p| open_neighbours(&grid, p), |_| false);
+        for (&end, &dist) in reachable.distances() {
+            let search = astar(
+                [start],
+                |&p| open_neighbours(&grid, p),
+                |_, _| 1,
+                |p| p.x.abs_diff(end.x) + p.y.abs_diff(end.y),
+                |&p| p == end,
+            );
+            assert_eq!(search.goal_distance(), Some(dist), "distance to {end:?}");
+            assert_eq!(search.path().unwrap().len(), dist + 1);
+        }
+    }
+}
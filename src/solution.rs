@@ -0,0 +1,118 @@
+use crate::locate_parse_error;
+use anyhow::{Context, Error, Result};
+use std::{any::Any, fmt::Display, ops::RangeInclusive, str::FromStr};
+
+/// A puzzle solution, split into a parsing phase and the two puzzle parts.
+pub trait Solution {
+    type Parsed: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> Result<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed) -> Result<Self::Answer2>;
+
+    /// Parses the input and solves both parts.
+    fn solve(input: &str) -> Result<(Self::Answer1, Self::Answer2)> {
+        let parsed = Self::parse(input).map_err(|e| locate_parse_error(input, e))?;
+        Ok((Self::part1(&parsed)?, Self::part2(&parsed)?))
+    }
+}
+
+/// A type-erased `Solution` for a single day, as stored in the registry.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    day: u8,
+    parse: fn(&str) -> Result<Box<dyn Any>>,
+    part1: fn(&dyn Any) -> Result<String>,
+    part2: fn(&dyn Any) -> Result<String>,
+}
+
+impl Puzzle {
+    pub const fn new<S: Solution>(day: u8) -> Self {
+        Self {
+            day,
+            parse: parse_erased::<S>,
+            part1: part1_erased::<S>,
+            part2: part2_erased::<S>,
+        }
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// The name of the input file and of the binary for this day, e.g. `day07`.
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        (self.parse)(input)
+    }
+
+    pub fn part1(&self, parsed: &dyn Any) -> Result<String> {
+        (self.part1)(parsed)
+    }
+
+    pub fn part2(&self, parsed: &dyn Any) -> Result<String> {
+        (self.part2)(parsed)
+    }
+
+    pub fn solve(&self, input: &str) -> Result<[String; 2]> {
+        let parsed = self.parse(input)?;
+        Ok([self.part1(&*parsed)?, self.part2(&*parsed)?])
+    }
+}
+
+fn parse_erased<S: Solution>(input: &str) -> Result<Box<dyn Any>> {
+    let parsed = S::parse(input).map_err(|e| locate_parse_error(input, e))?;
+    Ok(Box::new(parsed))
+}
+
+fn part1_erased<S: Solution>(parsed: &dyn Any) -> Result<String> {
+    Ok(S::part1(downcast::<S>(parsed))?.to_string())
+}
+
+fn part2_erased<S: Solution>(parsed: &dyn Any) -> Result<String> {
+    Ok(S::part2(downcast::<S>(parsed))?.to_string())
+}
+
+fn downcast<S: Solution>(parsed: &dyn Any) -> &S::Parsed {
+    parsed
+        .downcast_ref()
+        .expect("parsed input passed to the wrong puzzle")
+}
+
+/// A set of days selected on the command line, e.g. `7`, `1..=14`, `3..5` or `all`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaySelection(RangeInclusive<u8>);
+
+impl DaySelection {
+    pub fn all() -> Self {
+        Self(1..=u8::MAX)
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.0.contains(&day)
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = if s == "all" {
+            return Ok(Self::all());
+        } else if let Some((start, end)) = s.split_once("..=") {
+            start.parse()?..=end.parse()?
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end: u8 = end.parse()?;
+            start.parse()?..=end.checked_sub(1).context("empty day range")?
+        } else {
+            let day = s.parse()?;
+            day..=day
+        };
+        Ok(Self(range))
+    }
+}
@@ -0,0 +1,249 @@
+use crate::Grid;
+use std::collections::{hash_map, HashMap};
+
+/// The smallest rectangle containing a set of cells, with inclusive bounds.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub min_y: i64,
+    pub max_x: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    /// The bounds of the single cell `(x, y)`.
+    pub fn at(x: i64, y: i64) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+
+    pub fn width(&self) -> u64 {
+        self.max_x.abs_diff(self.min_x) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.max_y.abs_diff(self.min_y) + 1
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y)
+    }
+
+    /// Grows the bounds to include `(x, y)`.
+    pub fn extend(&mut self, x: i64, y: i64) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+}
+
+/// An unbounded two-dimensional grid, storing only the occupied cells.
+///
+/// Coordinates are signed, with `x` increasing to the right and `y` increasing downwards, and the
+/// grid keeps track of the bounding box of its occupied cells.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(i64, i64), T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The bounding box of the occupied cells, or `None` if the grid is empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.cells.contains_key(&(x, y))
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        self.cells.get(&(x, y))
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        self.cells.get_mut(&(x, y))
+    }
+
+    /// Occupies the cell `(x, y)` with `value`, returning its previous value.
+    pub fn insert(&mut self, x: i64, y: i64, value: T) -> Option<T> {
+        self.extend_bounds(x, y);
+        self.cells.insert((x, y), value)
+    }
+
+    /// The value of the cell `(x, y)`, which is occupied with `f()` if it is empty.
+    pub fn get_or_insert_with(&mut self, x: i64, y: i64, f: impl FnOnce() -> T) -> &mut T {
+        if !self.contains(x, y) {
+            self.extend_bounds(x, y);
+        }
+        self.cells.entry((x, y)).or_insert_with(f)
+    }
+
+    /// Empties the cell `(x, y)`, returning its previous value.
+    pub fn remove(&mut self, x: i64, y: i64) -> Option<T> {
+        let value = self.cells.remove(&(x, y))?;
+        let bounds = self.bounds.unwrap();
+        if x == bounds.min_x || x == bounds.max_x || y == bounds.min_y || y == bounds.max_y {
+            self.bounds = None;
+            for &(x, y) in self.cells.keys() {
+                Self::extend(&mut self.bounds, x, y);
+            }
+        }
+        Some(value)
+    }
+
+    /// The occupied cells and their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((i64, i64), &mut T)> {
+        self.cells.iter_mut().map(|(&p, v)| (p, v))
+    }
+
+    /// The coordinates of the occupied cells, in no particular order.
+    pub fn keys(&self) -> hash_map::Keys<'_, (i64, i64), T> {
+        self.cells.keys()
+    }
+
+    /// Renders the bounding box row by row, with one character per cell and a newline after each
+    /// row.
+    pub fn render(&self, mut f: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(b) = self.bounds else {
+            return String::new();
+        };
+        let mut s = String::new();
+        for y in b.min_y..=b.max_y {
+            s.extend((b.min_x..=b.max_x).map(|x| f(self.get(x, y))));
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Converts the part of the grid within `bounds` into a dense grid, calling `f` for each cell.
+    pub fn to_grid<U>(&self, bounds: Bounds, mut f: impl FnMut(Option<&T>) -> U) -> Grid<U> {
+        Grid::from_fn(bounds.width() as usize, bounds.height() as usize, |p| {
+            f(self.get(bounds.min_x + p.x as i64, bounds.min_y + p.y as i64))
+        })
+    }
+
+    fn extend_bounds(&mut self, x: i64, y: i64) {
+        Self::extend(&mut self.bounds, x, y);
+    }
+
+    fn extend(bounds: &mut Option<Bounds>, x: i64, y: i64) {
+        match bounds {
+            Some(b) => b.extend(x, y),
+            None => *bounds = Some(Bounds::at(x, y)),
+        }
+    }
+}
+
+impl<T> FromIterator<((i64, i64), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = ((i64, i64), T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for ((x, y), value) in iter {
+            grid.insert(x, y, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_get() {
+        let mut grid = SparseGrid::new();
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.insert(-3, 2, 'a'), None);
+        assert_eq!(grid.insert(-3, 2, 'b'), Some('a'));
+        *grid.get_or_insert_with(5, -1, || 'c') = 'd';
+        assert_eq!(*grid.get_or_insert_with(5, -1, || 'e'), 'd');
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(-3, 2), Some(&'b'));
+        assert_eq!(grid.get(5, -1), Some(&'d'));
+        assert_eq!(grid.get(0, 0), None);
+        assert!(grid.contains(-3, 2));
+        assert!(!grid.contains(2, -3));
+    }
+
+    #[test]
+    fn bounds() {
+        let mut grid: SparseGrid<()> = [((1, 1), ()), ((-2, 4), ()), ((3, -1), ())]
+            .into_iter()
+            .collect();
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            (bounds.min_x, bounds.min_y, bounds.max_x, bounds.max_y),
+            (-2, -1, 3, 4)
+        );
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert!(bounds.contains(0, 0));
+        assert!(!bounds.contains(4, 0));
+        grid.remove(1, 1);
+        assert_eq!(grid.bounds(), Some(bounds));
+        grid.remove(3, -1);
+        assert_eq!(grid.bounds(), Some(Bounds::at(-2, 4)));
+        assert_eq!(grid.remove(3, -1), None);
+        grid.remove(-2, 4);
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn iteration() {
+        let mut grid: SparseGrid<i32> = [((0, 0), 1), ((-1, 5), 2)].into_iter().collect();
+        for (_, value) in grid.iter_mut() {
+            *value *= 10;
+        }
+        let mut cells: Vec<_> = grid.iter().map(|(p, &v)| (p, v)).collect();
+        cells.sort();
+        assert_eq!(cells, [((-1, 5), 20), ((0, 0), 10)]);
+        assert_eq!(grid.keys().count(), 2);
+    }
+
+    #[test]
+    fn render() {
+        let grid: SparseGrid<char> = [((-1, -1), 'a'), ((1, 0), 'b')].into_iter().collect();
+        assert_eq!(grid.render(|c| *c.unwrap_or(&'.')), "a..\n..b\n");
+        assert_eq!(SparseGrid::<char>::new().render(|_| '.'), "");
+
+        let dense = grid.to_grid(Bounds::at(1, 0), |c| c.copied());
+        assert_eq!((dense.width(), dense.height()), (1, 1));
+        assert_eq!(dense[0], Some('b'));
+        let mut bounds = grid.bounds().unwrap();
+        bounds.extend(2, 1);
+        let dense = grid.to_grid(bounds, |c| c.is_some());
+        assert_eq!((dense.width(), dense.height()), (4, 3));
+        assert_eq!(dense.iter().filter(|&&c| c).count(), 2);
+    }
+}
@@ -0,0 +1,119 @@
+//! Checks every day against the answers recorded next to its inputs.
+//!
+//! For each day, the real input `inputs/dayNN` must have an answers file `inputs/dayNN.answers`.
+//! Only a day with neither, e.g. a new day before the input is downloaded, is checked without
+//! it. The example inputs `inputs/dayNN.exampleN` are checked against their answers files as well.
+
+use anyhow::{Context, Result};
+use aoc2022::{
+    answers::{answers_path, read_answers},
+    example_paths, input_path, puzzle,
+};
+use std::{fmt::Write, path::Path};
+
+fn check_day(day: u8) {
+    let puzzle = puzzle(day).unwrap();
+    let name = puzzle.name();
+    let mut failures = String::new();
+    let input = Some(input_path(&name)).filter(|path| path.exists() || answers_path(path).exists());
+    for path in input.into_iter().chain(example_paths(&name)) {
+        if let Err(e) = check_input(day, &path, &mut failures) {
+            writeln!(failures, "{}: {e:#}", path.display()).unwrap();
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "answers for day {day} differ:\n{failures}"
+    );
+}
+
+fn check_input(day: u8, path: &Path, failures: &mut String) -> Result<()> {
+    let input = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let expected = read_answers(path)?;
+    let puzzle = puzzle(day)?;
+    let parsed = puzzle.parse(&input)?;
+    for (part, expected) in expected {
+        let actual = match part {
+            1 => puzzle.part1(&*parsed)?,
+            2 => puzzle.part2(&*parsed)?,
+            _ => anyhow::bail!("invalid part {part}"),
+        };
+        if actual != expected {
+            writeln!(failures, "{}, part {part}:", path.display())?;
+            failures.push_str(&diff(&expected, &actual));
+        }
+    }
+    Ok(())
+}
+
+/// A line-by-line diff, with `-` marking expected and `+` marking actual lines.
+fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut s = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(i), actual.get(i));
+        if e == a {
+            writeln!(s, "   {}", e.unwrap()).unwrap();
+            continue;
+        }
+        if let Some(e) = e {
+            writeln!(s, "  -{e}").unwrap();
+        }
+        if let Some(a) = a {
+            writeln!(s, "  +{a}").unwrap();
+        }
+    }
+    s
+}
+
+macro_rules! answer_tests {
+    ($($name:ident => $day:literal,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+
+        const TESTED_DAYS: &[u8] = &[$($day),*];
+    };
+}
+
+answer_tests! {
+    day01 => 1,
+    day02 => 2,
+    day03 => 3,
+    day04 => 4,
+    day05 => 5,
+    day06 => 6,
+    day07 => 7,
+    day08 => 8,
+    day09 => 9,
+    day10 => 10,
+    day11 => 11,
+    day12 => 12,
+    day13 => 13,
+    day14 => 14,
+}
+
+#[test]
+fn all_days_tested() {
+    let days: Vec<u8> = aoc2022::PUZZLES.iter().map(|p| p.day()).collect();
+    assert_eq!(days, TESTED_DAYS);
+}
+
+#[test]
+fn day10_letters() {
+    use aoc2022::{day10::Day10, Solution};
+
+    let read = |path: &Path| {
+        let input = std::fs::read_to_string(path).unwrap();
+        Day10::part2(&Day10::parse(&input).unwrap()).unwrap()
+    };
+    assert_eq!(read(&input_path("day10")).text().unwrap(), "REHPRLUB");
+    // The example only draws a test pattern.
+    let example = read(&example_paths("day10").next().unwrap());
+    assert!(example.text().is_err());
+}
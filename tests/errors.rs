@@ -0,0 +1,187 @@
+//! Checks that small or malformed inputs are reported as errors instead of panicking.
+
+use aoc2022::{
+    day06::start_pos, day09::Rope, day11::Day11, day12::Map, day13, puzzle, Grid, Solution,
+};
+
+/// Solves `input` for `day` and returns the error with its causes.
+fn solve_error(day: u8, input: &str) -> String {
+    let error = puzzle(day).unwrap().solve(input).unwrap_err();
+    format!("{error:#}")
+}
+
+fn assert_error(day: u8, input: &str, expected: &str) {
+    let error = solve_error(day, input);
+    assert!(
+        error.contains(expected),
+        "day {day}: expected {expected:?} in {error:?}"
+    );
+}
+
+#[test]
+fn day01_too_few_elves() {
+    assert_error(1, "", "no elves in input");
+    assert_error(1, "1000\n\n2000\n", "expected at least 3 elves, found 2");
+}
+
+#[test]
+fn day01_overflow() {
+    assert_error(1, "18446744073709551615\n1\n", "total calories too large");
+    assert_error(
+        1,
+        "9223372036854775808\n\n9223372036854775808\n\n1\n",
+        "total calories too large",
+    );
+}
+
+#[test]
+fn day02_invalid_codes() {
+    assert_error(2, "A X\nB Q\n", "round 2: invalid response code 'Q'");
+    assert_error(2, "D X\n", "round 1: invalid opponent code 'D'");
+    assert_error(2, "A\n", "line too short");
+}
+
+#[test]
+fn day03_malformed_rucksacks() {
+    assert_error(3, "abcab\n", "rucksack 1: 5 items cannot be split");
+    assert_error(
+        3,
+        "acba\nabcd\n",
+        "rucksack 2: expected exactly one common item, found 0",
+    );
+    assert_error(
+        3,
+        "abab\n",
+        "rucksack 1: expected exactly one common item, found 2",
+    );
+    assert_error(
+        3,
+        "aa\naa\naa\naa\n",
+        "group 2: expected 3 rucksacks, found 1",
+    );
+}
+
+#[test]
+fn day06_short_packets() {
+    assert!(start_pos(b"abc", 4).is_err());
+    assert!(start_pos(b"", 4).is_err());
+    assert_eq!(start_pos(b"abcd", 4).unwrap(), 4);
+    assert_eq!(start_pos(b"aaabcd", 4).unwrap(), 6);
+    let error = start_pos(b"ab1cd", 4).unwrap_err();
+    assert_eq!(error.to_string(), "invalid character '1' in packet");
+    assert!(start_pos(b"abcd\xff", 4).is_err());
+    assert_error(6, "abc", "no start marker found");
+}
+
+#[test]
+fn day07_no_directory_to_delete() {
+    assert_error(7, "$ cd /\n$ ls\n100 a\n", "already enough free space");
+    assert_error(
+        7,
+        "$ cd /\n$ ls\n18446744073709551615 a\n1 b\n",
+        "total size too large",
+    );
+}
+
+#[test]
+fn day08_invalid_heights() {
+    assert_error(8, "123\n4x6\n", "invalid tree height at line 2, column 2");
+}
+
+#[test]
+fn day09_rope_without_knots() {
+    let error = Rope::new(0).err().unwrap();
+    assert_eq!(error.to_string(), "a rope needs at least one knot");
+}
+
+#[test]
+fn day10_large_operands() {
+    let input = "addx 2147483647\n".repeat(150);
+    puzzle(10).unwrap().solve(&input).unwrap();
+}
+
+#[test]
+fn day11_malformed_monkeys() {
+    let monkey = |i: usize, divisor: u64, target: usize, item: u64| {
+        format!(
+            "Monkey {i}:\n  \
+             Starting items: {item}\n  \
+             Operation: new = old * old\n  \
+             Test: divisible by {divisor}\n    \
+             If true: throw to monkey {target}\n    \
+             If false: throw to monkey {target}\n"
+        )
+    };
+    assert_error(
+        11,
+        &monkey(0, 2, 0, 1),
+        "expected at least 2 monkeys, found 1",
+    );
+    assert_error(11, "", "expected at least 2 monkeys, found 0");
+    let troop = [monkey(0, 2, 1, 1), monkey(1, 3, 2, 1)].join("\n");
+    assert_error(
+        11,
+        &troop,
+        "monkey 1 throws to monkey 2, but there are only 2 monkeys",
+    );
+    let troop = [monkey(0, 0, 1, 1), monkey(1, 3, 0, 1)].join("\n");
+    assert_error(11, &troop, "invalid divisor: must be positive");
+    let troop = [monkey(0, 2, 1, 1 << 33), monkey(1, 3, 0, 1)].join("\n");
+    assert_error(
+        11,
+        &troop,
+        "monkey 0 failed: worry level 8589934592 overflows",
+    );
+    let mut troop = Day11::parse(&[monkey(0, 2, 1, 1), monkey(1, 3, 0, 1)].join("\n")).unwrap();
+    let error = troop.play_rounds(1, 0).unwrap_err();
+    assert_eq!(error.to_string(), "cannot divide worry levels by zero");
+}
+
+#[test]
+fn day12_no_path() {
+    assert_error(12, "SbcdE\n", "no path to target found");
+    assert_error(12, "Sbcd\n", "no end position found");
+    assert_error(12, "", "no data in grid");
+    assert_error(12, "abcE\n", "no start position found");
+
+    // Heights of zero cannot come from the parser, but must not underflow either.
+    let map = Map {
+        heights: Grid::from_vec(vec![0, 0, 1], 3).unwrap(),
+        start: 0,
+        end: 2,
+    };
+    assert_eq!(map.find_path(Map::is_start), Some(2));
+}
+
+#[test]
+fn day13_deep_packets() {
+    let depth = day13::MAX_DEPTH;
+    let packet = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    let input = format!("{}\n{}\n", packet(depth), packet(depth - 1));
+    puzzle(13).unwrap().solve(&input).unwrap();
+    let input = format!("{}\n[]\n", packet(depth + 1));
+    assert_error(13, &input, "packet nested more than 1000 levels deep");
+    assert_error(13, "[[2]]\n[1]\n", "input contains divider packet");
+}
+
+#[test]
+fn day14_blocked_source() {
+    assert_error(14, "499,0 -> 501,0\n", "no sand reached the void");
+    assert_error(
+        14,
+        "0,9223372036854775807 -> 1,9223372036854775807\n",
+        "rock too deep",
+    );
+}
+
+#[test]
+fn day14_distant_rock() {
+    assert_error(14, "0,0 -> 0,4000000000\n", "rock too deep at 0,4000000000");
+    assert_error(14, "500,1001 -> 501,1001\n", "rock too deep at 500,1001");
+    assert_error(14, "-1000,5 -> 2000,5\n", "rock too far out at -1000,5");
+    assert_error(
+        14,
+        "500,-1 -> 500,1\n",
+        "rock at 500,-1 is above the source",
+    );
+}
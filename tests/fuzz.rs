@@ -0,0 +1,21 @@
+//! Fuzzes every day with generated inputs, small ones for many seeds and larger ones for a few.
+
+use aoc2022::{fuzz::fuzz, PUZZLES};
+
+#[test]
+fn small_inputs() {
+    for puzzle in PUZZLES {
+        if let Err(e) = fuzz(puzzle, 0..50, 10) {
+            panic!("{e:#}");
+        }
+    }
+}
+
+#[test]
+fn large_inputs() {
+    for puzzle in PUZZLES {
+        if let Err(e) = fuzz(puzzle, 0..3, 100) {
+            panic!("{e:#}");
+        }
+    }
+}
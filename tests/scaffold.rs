@@ -0,0 +1,58 @@
+//! Scaffolds a new day in a copy of this crate and checks that its answer test passes, without a
+//! real input and without network access. Building the copy is slow, so the test only runs with
+//! `cargo test -- --ignored`.
+
+use aoc2022::{scaffold::new_day, INPUTS_DIR_VAR};
+use std::{fs, io, path::Path, process::Command};
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let to = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &to)?;
+        } else {
+            fs::copy(entry.path(), to)?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[ignore = "builds a copy of the crate"]
+fn new_day_passes_answer_test() {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // The copy is built in its own target directory, which is kept to speed up later runs.
+    let scratch = Path::new(env!("CARGO_TARGET_TMPDIR")).join("scaffold");
+    let root = scratch.join("crate");
+    let _ = fs::remove_dir_all(&root);
+    for dir in ["src", "tests"] {
+        copy_dir(&source.join(dir), &root.join(dir)).unwrap();
+    }
+    fs::create_dir(root.join("inputs")).unwrap();
+    for file in ["Cargo.toml", "Cargo.lock", "rust-toolchain.toml"] {
+        if source.join(file).exists() {
+            fs::copy(source.join(file), root.join(file)).unwrap();
+        }
+    }
+
+    new_day(&root, 25, 2022).unwrap();
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["test", "--offline", "--test", "answers", "--", "--exact"])
+        .args(["day25", "all_days_tested"])
+        .current_dir(&root)
+        .env("CARGO_TARGET_DIR", scratch.join("target"))
+        .env_remove(INPUTS_DIR_VAR)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("test day25 ... ok"), "{stdout}");
+    assert!(stdout.contains("2 passed"), "{stdout}");
+}